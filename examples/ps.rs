@@ -11,8 +11,8 @@ fn main() {
 	thread::sleep(Duration::from_secs(1));
 
 	println!(
-		"{:>6} {:>4} {:>4} {:.100}",
-		"PID", "%CPU", "%MEM", "COMMAND"
+		"{:<10} {:>6} {:>4} {:>4} {:.100}",
+		"USER", "PID", "%CPU", "%MEM", "COMMAND"
 	);
 
 	for p in processes {
@@ -20,7 +20,8 @@ fn main() {
 
 		// TODO the percent formatting is not working
 		println!(
-			"{:>6} {:>2.1} {:>2.1} {:.100}",
+			"{:<10.10} {:>6} {:>2.1} {:>2.1} {:.100}",
+			p.username().unwrap(),
 			p.pid(),
 			p.cpu_percent().unwrap(),
 			p.memory_percent().unwrap(),
@@ -0,0 +1,147 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use std::time::Duration;
+
+use crate::cgroup::{Cgroup, CgroupVersion};
+use crate::{Result, TICKS_PER_SECOND};
+
+/// New struct, not in Python psutil.
+///
+/// The CPU bandwidth limit of a cgroup, from `cpu.max` or `cpu.cfs_quota_us` and
+/// `cpu.cfs_period_us` in v1.
+#[cfg_attr(feature = "serde", serde(crate = "renamed_serde"))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CpuMax {
+	/// CPU time the cgroup may use in each period, or `None` if it is unlimited.
+	pub quota: Option<Duration>,
+	pub period: Duration,
+}
+
+impl CpuMax {
+	/// The number of CPUs the quota amounts to, such as 1.5, or `None` if it is unlimited.
+	pub fn cpus(&self) -> Option<f64> {
+		self.quota
+			.map(|quota| quota.as_micros() as f64 / self.period.as_micros() as f64)
+	}
+}
+
+/// New struct, not in Python psutil.
+///
+/// CPU usage of a cgroup, from `cpu.stat` or `cpuacct.usage`, `cpuacct.stat` and `cpu.stat` in
+/// v1.
+#[cfg_attr(feature = "serde", serde(crate = "renamed_serde"))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CgroupCpuStat {
+	pub usage: Duration,
+	pub user: Duration,
+	pub system: Duration,
+
+	/// Number of enforcement periods that have elapsed.
+	pub nr_periods: u64,
+
+	/// Number of periods in which the cgroup was throttled.
+	pub nr_throttled: u64,
+
+	/// Time the cgroup was throttled for.
+	pub throttled: Duration,
+}
+
+impl Cgroup {
+	pub fn cpu_max(&self) -> Result<CpuMax> {
+		match self.version("cpu") {
+			Some(CgroupVersion::V1) => {
+				let quota = self.read("cpu", "cpu.cfs_quota_us")?;
+				let quota = quota.parse_i64(quota.contents.trim())?;
+				let period = self.read("cpu", "cpu.cfs_period_us")?.single()?;
+
+				Ok(CpuMax {
+					// -1 when unlimited
+					quota: Some(quota)
+						.filter(|quota| *quota >= 0)
+						.map(|quota| Duration::from_micros(quota as u64)),
+					period: Duration::from_micros(period),
+				})
+			}
+			_ => {
+				// such as `max 100000`
+				let contents = self.read("cpu", "cpu.max")?;
+				let fields = contents.contents.split_whitespace().collect::<Vec<_>>();
+				if fields.len() != 2 {
+					return Err(contents.missing_data());
+				}
+
+				let quota = match fields[0] {
+					"max" => None,
+					quota => Some(Duration::from_micros(contents.parse_u64(quota)?)),
+				};
+
+				Ok(CpuMax {
+					quota,
+					period: Duration::from_micros(contents.parse_u64(fields[1])?),
+				})
+			}
+		}
+	}
+
+	pub fn cpu_stat(&self) -> Result<CgroupCpuStat> {
+		if self.version("cpu") == Some(CgroupVersion::V2) {
+			let stat = self.read("cpu", "cpu.stat")?.flat_keyed()?;
+			let get = |key: &str| stat.get(key).copied().unwrap_or_default();
+
+			// the throttling statistics are only present if the cpu controller is enabled
+			return Ok(CgroupCpuStat {
+				usage: Duration::from_micros(get("usage_usec")),
+				user: Duration::from_micros(get("user_usec")),
+				system: Duration::from_micros(get("system_usec")),
+				nr_periods: get("nr_periods"),
+				nr_throttled: get("nr_throttled"),
+				throttled: Duration::from_micros(get("throttled_usec")),
+			});
+		}
+
+		let usage = self.read("cpuacct", "cpuacct.usage")?.single()?;
+		let times = self.read("cpuacct", "cpuacct.stat")?.flat_keyed()?;
+		let ticks = |key: &str| {
+			Duration::from_secs_f64(
+				times.get(key).copied().unwrap_or_default() as f64 / *TICKS_PER_SECOND,
+			)
+		};
+		let stat = self.read("cpu", "cpu.stat")?.flat_keyed()?;
+		let get = |key: &str| stat.get(key).copied().unwrap_or_default();
+
+		Ok(CgroupCpuStat {
+			usage: Duration::from_nanos(usage),
+			user: ticks("user"),
+			system: ticks("system"),
+			nr_periods: get("nr_periods"),
+			nr_throttled: get("nr_throttled"),
+			throttled: Duration::from_nanos(get("throttled_time")),
+		})
+	}
+}
+
+#[cfg(test)]
+mod unit_tests {
+	use super::*;
+
+	#[test]
+	fn test_cpu_max() {
+		let cpu_max = CpuMax {
+			quota: Some(Duration::from_millis(150)),
+			period: Duration::from_millis(100),
+		};
+
+		assert_eq!(cpu_max.cpus(), Some(1.5));
+		assert_eq!(
+			CpuMax {
+				quota: None,
+				..cpu_max
+			}
+			.cpus(),
+			None
+		);
+	}
+}
@@ -0,0 +1,293 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::cgroup::mount::{cgroup_mounts, CgroupMount};
+use crate::cgroup::{procfs_cgroups, ProcessCgroup};
+use crate::{read_file, Error, Pid, Result};
+
+/// New enum, not in Python psutil.
+///
+/// Version of the cgroup hierarchy a controller is read from.
+#[cfg_attr(feature = "serde", serde(crate = "renamed_serde"))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CgroupVersion {
+	V1,
+	V2,
+}
+
+/// New struct, not in Python psutil.
+///
+/// A control group, whose files are read from the v1 hierarchy of a controller if it is mounted,
+/// and from the unified (v2) hierarchy otherwise.
+#[derive(Clone, Debug, Default)]
+pub struct Cgroup {
+	pub(crate) unified: Option<PathBuf>,
+	/// Directories in the v1 hierarchies, by controller.
+	pub(crate) controllers: HashMap<String, PathBuf>,
+	/// Where the hierarchies are mounted, above which there are no parents.
+	pub(crate) mount_points: Vec<PathBuf>,
+}
+
+impl Cgroup {
+	/// The cgroup with the same path in every hierarchy, such as `/system.slice/foo.service`.
+	pub fn new<P: AsRef<Path>>(path: P) -> Result<Cgroup> {
+		let mut cgroup = Cgroup::default();
+
+		for mount in cgroup_mounts()? {
+			cgroup.insert(&mount, path.as_ref());
+		}
+
+		Ok(cgroup)
+	}
+
+	/// The cgroups of a process, such as those returned by `ProcessExt::cgroups`.
+	pub fn from_process_cgroups(process_cgroups: &[ProcessCgroup]) -> Result<Cgroup> {
+		let mounts = cgroup_mounts()?;
+		let mut cgroup = Cgroup::default();
+
+		for process_cgroup in process_cgroups {
+			let mount = mounts.iter().find(|mount| {
+				if process_cgroup.is_unified() {
+					mount.unified
+				} else {
+					!mount.unified
+						&& !process_cgroup.controllers.is_empty()
+						&& process_cgroup
+							.controllers
+							.iter()
+							.all(|controller| mount.controllers.contains(controller))
+				}
+			});
+
+			if let Some(mount) = mount {
+				cgroup.insert(mount, &process_cgroup.path);
+			}
+		}
+
+		Ok(cgroup)
+	}
+
+	/// The cgroup of a process.
+	pub fn for_pid(pid: Pid) -> Result<Cgroup> {
+		Cgroup::from_process_cgroups(&procfs_cgroups(pid)?)
+	}
+
+	/// The cgroup of the current process, which is the one of the container when running in one.
+	pub fn current() -> Result<Cgroup> {
+		Cgroup::for_pid(std::process::id())
+	}
+
+	fn insert(&mut self, mount: &CgroupMount, path: &Path) {
+		let dir = mount.dir(path);
+		self.mount_points.push(mount.mount_point.clone());
+
+		if mount.unified {
+			self.unified = Some(dir);
+		} else {
+			for controller in mount.controllers.iter() {
+				self.controllers.insert(controller.clone(), dir.clone());
+			}
+		}
+	}
+
+	/// The parent cgroup, or `None` if this is the root of every hierarchy, which is the cgroup of
+	/// the container when running in one with a cgroup namespace.
+	/// Directories that are already at the root of their hierarchy are kept as they are.
+	pub fn parent(&self) -> Option<Cgroup> {
+		let parent_dir = |dir: &PathBuf| {
+			if self.mount_points.contains(dir) {
+				None
+			} else {
+				dir.parent().map(Path::to_path_buf)
+			}
+		};
+
+		let unified = self.unified.as_ref().and_then(parent_dir);
+		let controllers = self
+			.controllers
+			.iter()
+			.filter_map(|(controller, dir)| Some((controller.clone(), parent_dir(dir)?)))
+			.collect::<HashMap<_, _>>();
+		if unified.is_none() && controllers.is_empty() {
+			return None;
+		}
+
+		let mut parent = self.clone();
+		parent.unified = unified.or(parent.unified);
+		parent.controllers.extend(controllers);
+
+		Some(parent)
+	}
+
+	/// This cgroup followed by its parents up to the root of the hierarchies.
+	/// Limits of the ancestors also apply, such as the `MemoryMax` of a systemd slice.
+	pub fn ancestors(&self) -> impl Iterator<Item = Cgroup> {
+		std::iter::successors(Some(self.clone()), Cgroup::parent)
+	}
+
+	/// The directory in the unified hierarchy.
+	pub fn unified_dir(&self) -> Option<&Path> {
+		self.unified.as_deref()
+	}
+
+	/// The directory in the v1 hierarchy of the controller, such as `memory`.
+	pub fn controller_dir(&self, controller: &str) -> Option<&Path> {
+		self.controllers.get(controller).map(PathBuf::as_path)
+	}
+
+	/// The version the controller is read from, or `None` if it is not mounted.
+	pub fn version(&self, controller: &str) -> Option<CgroupVersion> {
+		if self.controllers.contains_key(controller) {
+			Some(CgroupVersion::V1)
+		} else if self.unified.is_some() {
+			Some(CgroupVersion::V2)
+		} else {
+			None
+		}
+	}
+
+	/// Reads a file from the v1 hierarchy of the controller, or from the unified hierarchy if the
+	/// controller is not mounted as v1. See `version` for which name to use.
+	pub(crate) fn read(&self, controller: &str, name: &str) -> Result<Contents> {
+		let dir = self
+			.controller_dir(controller)
+			.or_else(|| self.unified_dir())
+			.ok_or_else(|| Error::MissingData {
+				path: PathBuf::from("/proc/self/mountinfo"),
+				contents: controller.to_string(),
+			})?;
+		let path = dir.join(name);
+		let contents = read_file(&path)?;
+
+		Ok(Contents { path, contents })
+	}
+}
+
+/// The contents of a file in a cgroup, with the helpers used to parse it.
+pub(crate) struct Contents {
+	pub(crate) path: PathBuf,
+	pub(crate) contents: String,
+}
+
+impl Contents {
+	pub(crate) fn parse_u64(&self, s: &str) -> Result<u64> {
+		s.parse().map_err(|err| Error::ParseInt {
+			path: self.path.clone(),
+			contents: self.contents.clone(),
+			source: err,
+		})
+	}
+
+	pub(crate) fn parse_u32(&self, s: &str) -> Result<u32> {
+		s.parse().map_err(|err| Error::ParseInt {
+			path: self.path.clone(),
+			contents: self.contents.clone(),
+			source: err,
+		})
+	}
+
+	pub(crate) fn parse_i64(&self, s: &str) -> Result<i64> {
+		s.parse().map_err(|err| Error::ParseInt {
+			path: self.path.clone(),
+			contents: self.contents.clone(),
+			source: err,
+		})
+	}
+
+	pub(crate) fn missing_data(&self) -> Error {
+		Error::MissingData {
+			path: self.path.clone(),
+			contents: self.contents.clone(),
+		}
+	}
+
+	/// Parses a single number.
+	pub(crate) fn single(&self) -> Result<u64> {
+		self.parse_u64(self.contents.trim())
+	}
+
+	/// Parses lines of keys and values, such as `cpu.stat` or `memory.stat`.
+	pub(crate) fn flat_keyed(&self) -> Result<HashMap<String, u64>> {
+		self.contents
+			.lines()
+			.filter(|line| !line.is_empty())
+			.map(|line| {
+				let (key, value) = line.split_once(' ').ok_or_else(|| self.missing_data())?;
+
+				Ok((key.to_string(), self.parse_u64(value.trim())?))
+			})
+			.collect()
+	}
+}
+
+#[cfg(test)]
+mod unit_tests {
+	use super::*;
+
+	#[test]
+	fn test_cgroup_ancestors() {
+		let cgroup = Cgroup {
+			unified: Some(PathBuf::from(
+				"/sys/fs/cgroup/unified/system.slice/foo.service",
+			)),
+			controllers: vec![
+				(
+					"memory".to_string(),
+					PathBuf::from("/sys/fs/cgroup/memory/system.slice"),
+				),
+				("pids".to_string(), PathBuf::from("/sys/fs/cgroup/pids")),
+			]
+			.into_iter()
+			.collect(),
+			mount_points: vec![
+				PathBuf::from("/sys/fs/cgroup/unified"),
+				PathBuf::from("/sys/fs/cgroup/memory"),
+				PathBuf::from("/sys/fs/cgroup/pids"),
+			],
+		};
+		let ancestors = cgroup.ancestors().collect::<Vec<_>>();
+
+		assert_eq!(ancestors.len(), 3);
+		assert_eq!(
+			ancestors[1].unified_dir(),
+			Some(Path::new("/sys/fs/cgroup/unified/system.slice"))
+		);
+		assert_eq!(
+			ancestors[1].controller_dir("memory"),
+			Some(Path::new("/sys/fs/cgroup/memory"))
+		);
+		assert_eq!(
+			ancestors[2].unified_dir(),
+			Some(Path::new("/sys/fs/cgroup/unified"))
+		);
+		assert_eq!(
+			ancestors[2].controller_dir("pids"),
+			Some(Path::new("/sys/fs/cgroup/pids"))
+		);
+	}
+
+	#[test]
+	fn test_cgroup_current() {
+		let cgroup = Cgroup::current().unwrap();
+		// the root cgroup has no limits and cgroups may not be mounted at all
+		let check = |result: Result<()>| match result {
+			Ok(()) => {}
+			Err(Error::ReadFile { source, .. })
+				if source.kind() == std::io::ErrorKind::NotFound => {}
+			Err(Error::MissingData { .. }) if cgroup.version("memory").is_none() => {}
+			Err(e) => panic!("{}", e),
+		};
+
+		check(cgroup.memory_current().map(|_| ()));
+		check(cgroup.memory_max().map(|_| ()));
+		check(cgroup.memory_stat().map(|_| ()));
+		check(cgroup.pids_current().map(|_| ()));
+		check(cgroup.cpu_max().map(|_| ()));
+		check(cgroup.cpu_stat().map(|_| ()));
+		check(cgroup.io_stat().map(|_| ()));
+	}
+}
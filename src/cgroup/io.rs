@@ -0,0 +1,168 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use std::collections::BTreeMap;
+
+use crate::cgroup::group::Contents;
+use crate::cgroup::{Cgroup, CgroupVersion};
+use crate::{Bytes, Count, Result};
+
+/// New struct, not in Python psutil.
+///
+/// I/O of a cgroup on a block device, from `io.stat` or `blkio.throttle.io_service_bytes` and
+/// `blkio.throttle.io_serviced` in v1.
+#[cfg_attr(feature = "serde", serde(crate = "renamed_serde"))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CgroupIoStat {
+	/// Major and minor number of the device.
+	pub device: (u32, u32),
+	pub read_bytes: Bytes,
+	pub write_bytes: Bytes,
+	pub read_count: Count,
+	pub write_count: Count,
+	pub discarded_bytes: Bytes,
+	pub discarded_count: Count,
+}
+
+fn parse_device(contents: &Contents, device: &str) -> Result<(u32, u32)> {
+	let (major, minor) = device
+		.split_once(':')
+		.ok_or_else(|| contents.missing_data())?;
+
+	Ok((contents.parse_u32(major)?, contents.parse_u32(minor)?))
+}
+
+/// Parses lines such as `8:0 rbytes=1 wbytes=2 rios=3 wios=4 dbytes=5 dios=6`.
+fn parse_io_stat(contents: &Contents) -> Result<Vec<CgroupIoStat>> {
+	let mut stats = Vec::new();
+
+	for line in contents.contents.lines() {
+		let mut fields = line.split_whitespace();
+		let device = match fields.next() {
+			Some(device) => parse_device(contents, device)?,
+			None => continue,
+		};
+		let mut stat = CgroupIoStat {
+			device,
+			..Default::default()
+		};
+
+		for field in fields {
+			let (key, value) = field
+				.split_once('=')
+				.ok_or_else(|| contents.missing_data())?;
+			// other keys, such as `cost.vrate=100.00` of iocost, are not always integers
+			let counter = match key {
+				"rbytes" => &mut stat.read_bytes,
+				"wbytes" => &mut stat.write_bytes,
+				"rios" => &mut stat.read_count,
+				"wios" => &mut stat.write_count,
+				"dbytes" => &mut stat.discarded_bytes,
+				"dios" => &mut stat.discarded_count,
+				_ => continue,
+			};
+
+			*counter = contents.parse_u64(value)?;
+		}
+
+		stats.push(stat);
+	}
+
+	Ok(stats)
+}
+
+/// Parses the v1 files, with lines such as `8:0 Read 1234` and a final `Total` line.
+fn parse_blkio(bytes: &Contents, count: &Contents) -> Result<Vec<CgroupIoStat>> {
+	let mut stats: BTreeMap<(u32, u32), CgroupIoStat> = BTreeMap::new();
+
+	for (contents, is_bytes) in [(bytes, true), (count, false)] {
+		for line in contents.contents.lines() {
+			let fields = line.split_whitespace().collect::<Vec<_>>();
+			if fields.len() != 3 {
+				continue;
+			}
+
+			let device = parse_device(contents, fields[0])?;
+			let value = contents.parse_u64(fields[2])?;
+			let stat = stats.entry(device).or_insert(CgroupIoStat {
+				device,
+				..Default::default()
+			});
+
+			match (fields[1], is_bytes) {
+				("Read", true) => stat.read_bytes = value,
+				("Write", true) => stat.write_bytes = value,
+				("Discard", true) => stat.discarded_bytes = value,
+				("Read", false) => stat.read_count = value,
+				("Write", false) => stat.write_count = value,
+				("Discard", false) => stat.discarded_count = value,
+				_ => {}
+			}
+		}
+	}
+
+	Ok(stats.into_values().collect())
+}
+
+impl Cgroup {
+	/// I/O of the cgroup for each block device it used.
+	pub fn io_stat(&self) -> Result<Vec<CgroupIoStat>> {
+		match self.version("blkio") {
+			Some(CgroupVersion::V1) => parse_blkio(
+				&self.read("blkio", "blkio.throttle.io_service_bytes")?,
+				&self.read("blkio", "blkio.throttle.io_serviced")?,
+			),
+			_ => parse_io_stat(&self.read("io", "io.stat")?),
+		}
+	}
+}
+
+#[cfg(test)]
+mod unit_tests {
+	use super::*;
+	use std::path::PathBuf;
+
+	fn contents(contents: &str) -> Contents {
+		Contents {
+			path: PathBuf::from("io.stat"),
+			contents: contents.to_string(),
+		}
+	}
+
+	#[test]
+	fn test_parse_io_stat() {
+		let stats = parse_io_stat(&contents(
+			"8:0 rbytes=1024 wbytes=2048 rios=1 wios=2 dbytes=0 dios=0 cost.vrate=100.00 depth=max\n259:0 rbytes=5 wbytes=0 rios=1 wios=0 dbytes=0 dios=0\n",
+		))
+		.unwrap();
+
+		assert_eq!(stats.len(), 2);
+		assert_eq!(stats[0].device, (8, 0));
+		assert_eq!(stats[0].write_bytes, 2048);
+		assert_eq!(stats[1].device, (259, 0));
+		assert_eq!(stats[1].read_count, 1);
+		assert!(parse_io_stat(&contents("4294967296:0 rbytes=1\n")).is_err());
+	}
+
+	#[test]
+	fn test_parse_blkio() {
+		let stats = parse_blkio(
+			&contents("8:0 Read 1024\n8:0 Write 2048\n8:0 Sync 0\n8:0 Total 3072\nTotal 3072\n"),
+			&contents("8:0 Read 1\n8:0 Write 2\n8:0 Total 3\nTotal 3\n"),
+		)
+		.unwrap();
+
+		assert_eq!(
+			stats,
+			vec![CgroupIoStat {
+				device: (8, 0),
+				read_bytes: 1024,
+				write_bytes: 2048,
+				read_count: 1,
+				write_count: 2,
+				..Default::default()
+			}]
+		);
+	}
+}
@@ -0,0 +1,42 @@
+use std::collections::HashMap;
+
+use crate::cgroup::{Cgroup, CgroupVersion};
+use crate::{Bytes, Result};
+
+/// v1 reports a limit close to `i64::MAX` when there is none.
+const V1_UNLIMITED: Bytes = 1 << 62;
+
+impl Cgroup {
+	/// Memory used by the cgroup, from `memory.current` or `memory.usage_in_bytes` in v1.
+	pub fn memory_current(&self) -> Result<Bytes> {
+		match self.version("memory") {
+			Some(CgroupVersion::V1) => self.read("memory", "memory.usage_in_bytes")?.single(),
+			_ => self.read("memory", "memory.current")?.single(),
+		}
+	}
+
+	/// The memory limit of the cgroup, from `memory.max` or `memory.limit_in_bytes` in v1.
+	/// Returns `None` if it is unlimited.
+	pub fn memory_max(&self) -> Result<Option<Bytes>> {
+		match self.version("memory") {
+			Some(CgroupVersion::V1) => {
+				let max = self.read("memory", "memory.limit_in_bytes")?.single()?;
+
+				Ok(Some(max).filter(|max| *max < V1_UNLIMITED))
+			}
+			_ => {
+				let contents = self.read("memory", "memory.max")?;
+
+				match contents.contents.trim() {
+					"max" => Ok(None),
+					_ => contents.single().map(Some),
+				}
+			}
+		}
+	}
+
+	/// The statistics in `memory.stat`, whose keys differ between v1 and v2.
+	pub fn memory_stat(&self) -> Result<HashMap<String, u64>> {
+		self.read("memory", "memory.stat")?.flat_keyed()
+	}
+}
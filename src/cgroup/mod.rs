@@ -0,0 +1,16 @@
+//! Limits and usage of control groups, read from `/sys/fs/cgroup`.
+//!
+//! Supports the unified (v2) hierarchy and falls back to the v1 controllers.
+
+mod cpu;
+mod group;
+mod io;
+mod memory;
+mod mount;
+mod pids;
+mod process_cgroup;
+
+pub use cpu::*;
+pub use group::*;
+pub use io::*;
+pub use process_cgroup::*;
@@ -0,0 +1,112 @@
+use std::path::{Path, PathBuf};
+
+use crate::{read_file, Result};
+
+const MOUNTINFO: &str = "/proc/self/mountinfo";
+
+/// A mounted cgroup hierarchy.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct CgroupMount {
+	/// The cgroup that is mounted, which is not `/` inside containers without a cgroup namespace.
+	pub(crate) root: PathBuf,
+	pub(crate) mount_point: PathBuf,
+	/// Empty for the unified hierarchy.
+	pub(crate) controllers: Vec<String>,
+	pub(crate) unified: bool,
+}
+
+impl CgroupMount {
+	/// The directory of a cgroup, given its path relative to the root of the hierarchy.
+	pub(crate) fn dir(&self, path: &Path) -> PathBuf {
+		let relative = path
+			.strip_prefix(&self.root)
+			.or_else(|_| path.strip_prefix("/"))
+			.unwrap_or(path);
+
+		// joining an empty path would add a trailing slash
+		if relative.as_os_str().is_empty() {
+			self.mount_point.clone()
+		} else {
+			self.mount_point.join(relative)
+		}
+	}
+}
+
+/// Parses the cgroup mounts out of `/proc/[pid]/mountinfo`, see `proc(5)`.
+pub(crate) fn parse_cgroup_mounts(contents: &str) -> Vec<CgroupMount> {
+	contents
+		.lines()
+		.filter_map(|line| {
+			// optional fields are terminated by a single hyphen
+			let (mount, superblock) = line.split_once(" - ")?;
+			let mount = mount.split_whitespace().collect::<Vec<_>>();
+			let superblock = superblock.split_whitespace().collect::<Vec<_>>();
+			if mount.len() < 5 || superblock.len() < 3 {
+				return None;
+			}
+
+			let unified = match superblock[0] {
+				"cgroup2" => true,
+				"cgroup" => false,
+				_ => return None,
+			};
+			let controllers = if unified {
+				Vec::new()
+			} else {
+				superblock[2]
+					.split(',')
+					// named hierarchies and mount options are not controllers
+					.filter(|option| {
+						!matches!(
+							*option,
+							"rw" | "ro"
+								| "xattr" | "noprefix" | "clone_children"
+								| "cpuset_v2_mode"
+						) && !option.contains('=')
+					})
+					.map(|option| option.to_string())
+					.collect()
+			};
+
+			Some(CgroupMount {
+				root: PathBuf::from(mount[3]),
+				mount_point: PathBuf::from(mount[4].replace("\\040", " ")),
+				controllers,
+				unified,
+			})
+		})
+		.collect()
+}
+
+pub(crate) fn cgroup_mounts() -> Result<Vec<CgroupMount>> {
+	Ok(parse_cgroup_mounts(&read_file(MOUNTINFO)?))
+}
+
+#[cfg(test)]
+mod unit_tests {
+	use super::*;
+
+	#[test]
+	fn test_parse_cgroup_mounts() {
+		let contents = "\
+24 1 8:1 / / rw,relatime - ext4 /dev/sda1 rw
+33 32 0:29 /docker/abc /sys/fs/cgroup/cpu,cpuacct ro,nosuid master:12 - cgroup cgroup ro,cpu,cpuacct
+41 32 0:37 / /sys/fs/cgroup/systemd rw,relatime - cgroup cgroup rw,xattr,name=systemd
+42 32 0:38 / /sys/fs/cgroup/unified rw,relatime - cgroup2 cgroup2 rw,nsdelegate
+";
+		let mounts = parse_cgroup_mounts(contents);
+
+		assert_eq!(mounts.len(), 3);
+		assert_eq!(mounts[0].controllers, vec!["cpu", "cpuacct"]);
+		assert_eq!(
+			mounts[0].dir(Path::new("/docker/abc")),
+			PathBuf::from("/sys/fs/cgroup/cpu,cpuacct")
+		);
+		assert!(mounts[1].controllers.is_empty());
+		assert!(mounts[2].unified);
+		assert_eq!(
+			mounts[2].dir(Path::new("/system.slice")),
+			PathBuf::from("/sys/fs/cgroup/unified/system.slice")
+		);
+	}
+}
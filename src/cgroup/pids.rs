@@ -0,0 +1,9 @@
+use crate::cgroup::Cgroup;
+use crate::{Count, Result};
+
+impl Cgroup {
+	/// Number of processes in the cgroup and its descendants, from `pids.current`.
+	pub fn pids_current(&self) -> Result<Count> {
+		self.read("pids", "pids.current")?.single()
+	}
+}
@@ -0,0 +1,93 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use std::path::PathBuf;
+
+use crate::{read_file, Error, Pid, Result};
+
+/// New struct, not in Python psutil.
+///
+/// A line of `/proc/[pid]/cgroup`: the cgroup of a process in one hierarchy.
+#[cfg_attr(feature = "serde", serde(crate = "renamed_serde"))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProcessCgroup {
+	/// 0 for the unified (v2) hierarchy.
+	pub hierarchy_id: u32,
+
+	/// Controllers bound to a v1 hierarchy, such as `cpu` and `cpuacct`, or named hierarchies
+	/// such as `name=systemd`. Empty for the unified hierarchy.
+	pub controllers: Vec<String>,
+
+	/// Path of the cgroup, relative to the root of the hierarchy.
+	pub path: PathBuf,
+}
+
+impl ProcessCgroup {
+	/// Whether this is the unified (v2) hierarchy.
+	pub fn is_unified(&self) -> bool {
+		self.hierarchy_id == 0 && self.controllers.is_empty()
+	}
+}
+
+pub(crate) fn parse_process_cgroups(contents: &str, path: PathBuf) -> Result<Vec<ProcessCgroup>> {
+	contents
+		.lines()
+		.map(|line| {
+			// `hierarchy-ID:controllers:path`, where the path may contain colons
+			let fields = match line.splitn(3, ':').collect::<Vec<_>>() {
+				fields if fields.len() == 3 => Ok(fields),
+				_ => Err(Error::MissingData {
+					path: path.clone(),
+					contents: line.to_string(),
+				}),
+			}?;
+
+			Ok(ProcessCgroup {
+				hierarchy_id: fields[0].parse().map_err(|err| Error::ParseInt {
+					path: path.clone(),
+					contents: line.to_string(),
+					source: err,
+				})?,
+				controllers: fields[1]
+					.split(',')
+					.filter(|controller| !controller.is_empty())
+					.map(|controller| controller.to_string())
+					.collect(),
+				path: PathBuf::from(fields[2]),
+			})
+		})
+		.collect()
+}
+
+/// New function, not in Python psutil.
+/// The cgroups of a process in every hierarchy, from `/proc/[pid]/cgroup`.
+pub fn procfs_cgroups(pid: Pid) -> Result<Vec<ProcessCgroup>> {
+	let path = PathBuf::from("/proc").join(pid.to_string()).join("cgroup");
+	let contents = read_file(&path)?;
+
+	parse_process_cgroups(&contents, path)
+}
+
+#[cfg(test)]
+mod unit_tests {
+	use super::*;
+
+	#[test]
+	fn test_parse_process_cgroups() {
+		let contents = "\
+12:cpu,cpuacct:/docker/abc
+1:name=systemd:/init.scope
+0::/system.slice/a:b.service
+";
+		let cgroups = parse_process_cgroups(contents, PathBuf::from("cgroup")).unwrap();
+
+		assert_eq!(cgroups.len(), 3);
+		assert_eq!(cgroups[0].hierarchy_id, 12);
+		assert_eq!(cgroups[0].controllers, vec!["cpu", "cpuacct"]);
+		assert_eq!(cgroups[0].path, PathBuf::from("/docker/abc"));
+		assert!(!cgroups[1].is_unified());
+		assert!(cgroups[2].is_unified());
+		assert_eq!(cgroups[2].path, PathBuf::from("/system.slice/a:b.service"));
+	}
+}
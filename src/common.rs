@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 
 #[cfg_attr(feature = "serde", serde(crate = "renamed_serde"))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum TcpConnectionStatus {
 	Established,
 	SynSent,
@@ -25,6 +26,7 @@ pub enum TcpConnectionStatus {
 
 #[cfg_attr(feature = "serde", serde(crate = "renamed_serde"))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum NetConnectionType {
 	Inet,
 	Inet4,
@@ -38,3 +40,37 @@ pub enum NetConnectionType {
 	Unix,
 	All,
 }
+
+/// Address family of a socket.
+#[cfg_attr(feature = "serde", serde(crate = "renamed_serde"))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AddressFamily {
+	/// IPv4 (`AF_INET`).
+	Inet,
+
+	/// IPv6 (`AF_INET6`).
+	Inet6,
+
+	/// Unix domain sockets (`AF_UNIX`).
+	Unix,
+
+	/// Link-layer addresses (`AF_PACKET` on Linux, `AF_LINK` on macOS and BSD).
+	/// Renamed from `AF_LINK` in Python psutil.
+	Link,
+}
+
+/// Type of a socket.
+#[cfg_attr(feature = "serde", serde(crate = "renamed_serde"))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SocketType {
+	/// `SOCK_STREAM`
+	Stream,
+
+	/// `SOCK_DGRAM`
+	Datagram,
+
+	/// `SOCK_SEQPACKET`
+	SeqPacket,
+}
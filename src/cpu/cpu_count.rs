@@ -7,3 +7,79 @@ pub fn cpu_count() -> Count {
 pub fn cpu_count_physical() -> Count {
 	num_cpus::get_physical() as Count
 }
+
+/// New function, not in Python psutil.
+///
+/// The number of CPUs the current process can use, for example to size a thread pool.
+/// On Linux, this is the smallest of the number of online CPUs, the number of CPUs in the
+/// affinity mask of the process, and the smallest CPU quota (`cpu.max`) of its cgroup and their
+/// ancestors, rounded up. Sources that cannot be read are ignored.
+/// Elsewhere, this is `cpu_count()`.
+pub fn cpu_count_effective() -> Count {
+	#[cfg(target_os = "linux")]
+	{
+		use nix::libc;
+		use nix::sched::{sched_getaffinity, CpuSet};
+		use nix::unistd::Pid;
+
+		use crate::cgroup::Cgroup;
+
+		// `cpu_count()` already applies the affinity mask and the quota of the cgroup itself
+		let online = match unsafe { libc::sysconf(libc::_SC_NPROCESSORS_ONLN) } {
+			online if online > 0 => online as Count,
+			_ => cpu_count(),
+		};
+		let affinity = sched_getaffinity(Pid::from_raw(0)).ok().map(|cpu_set| {
+			(0..CpuSet::count())
+				.filter(|cpu| cpu_set.is_set(*cpu).unwrap_or(false))
+				.count() as Count
+		});
+		let quota = Cgroup::current().ok().and_then(|cgroup| {
+			cgroup
+				.ancestors()
+				.filter_map(|cgroup| cgroup.cpu_max().ok()?.cpus())
+				.min_by(|a, b| a.total_cmp(b))
+		});
+
+		effective_cpu_count(online, affinity, quota)
+	}
+	#[cfg(not(target_os = "linux"))]
+	{
+		cpu_count()
+	}
+}
+
+#[cfg(target_os = "linux")]
+fn effective_cpu_count(online: Count, affinity: Option<Count>, quota: Option<f64>) -> Count {
+	let mut count = online;
+
+	if let Some(affinity) = affinity.filter(|affinity| *affinity > 0) {
+		count = count.min(affinity);
+	}
+	if let Some(quota) = quota {
+		count = count.min((quota.ceil() as Count).max(1));
+	}
+
+	count
+}
+
+#[cfg(test)]
+mod unit_tests {
+	use super::*;
+
+	#[test]
+	fn test_cpu_count_effective() {
+		assert!(cpu_count_effective() >= 1);
+	}
+
+	#[cfg(target_os = "linux")]
+	#[test]
+	fn test_effective_cpu_count() {
+		assert_eq!(effective_cpu_count(8, None, None), 8);
+		assert_eq!(effective_cpu_count(8, Some(4), None), 4);
+		assert_eq!(effective_cpu_count(8, Some(0), None), 8);
+		assert_eq!(effective_cpu_count(8, Some(4), Some(1.5)), 2);
+		assert_eq!(effective_cpu_count(8, None, Some(0.1)), 1);
+		assert_eq!(effective_cpu_count(2, None, Some(16.0)), 2);
+	}
+}
@@ -61,8 +61,8 @@ pub fn cpu_freq_percpu() -> io::Result<Vec<CpuFreq>> {
 			.filter(|c| c.is_ascii_digit())
 			.collect::<String>()
 			.parse::<usize>();
-		if cpu_num.is_ok() {
-			sorted_paths[cpu_num.unwrap()] = Some(path);
+		if let Ok(cpu_num) = cpu_num {
+			sorted_paths[cpu_num] = Some(path);
 		}
 	}
 
@@ -52,7 +52,7 @@ pub enum Error {
 	#[error("nix error: {}", source)]
 	NixError { source: nix::Error },
 
-	/// macOS only.
+	/// macOS and Linux.
 	#[error("OS error: {}", source)]
 	OsError { source: io::Error },
 }
@@ -120,7 +120,7 @@ pub(crate) fn glob(path: &str) -> Vec<Result<PathBuf>> {
 		.map(|result| {
 			result.map_err(|err| Error::ReadFile {
 				path: path.into(),
-				source: err.into_error(),
+				source: err.into(),
 			})
 		})
 		.collect()
@@ -35,6 +35,9 @@ mod types;
 pub use errors::*;
 pub use types::*;
 
+#[cfg(all(feature = "cgroup", target_os = "linux"))]
+pub mod cgroup;
+
 #[cfg(feature = "cpu")]
 pub mod cpu;
 
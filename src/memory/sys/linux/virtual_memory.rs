@@ -1,3 +1,4 @@
+use crate::cgroup::Cgroup;
 use crate::memory::{make_map, VirtualMemory};
 use crate::{read_file, Error, Result};
 
@@ -49,3 +50,77 @@ pub fn virtual_memory() -> Result<VirtualMemory> {
 		slab,
 	})
 }
+
+/// New function, not in Python psutil.
+///
+/// Like `virtual_memory()`, but limited by the memory limit of the cgroup of the current process,
+/// such as the limit of the container it runs in.
+///
+/// If the cgroup or one of its ancestors has a limit (`memory.max`) below the host total, the
+/// smallest one replaces `total`, and `available` is the smaller of the host value and the limit
+/// minus the memory the limited cgroup cannot reclaim: its usage (`memory.current`) without
+/// inactive file pages. `used` is then that unreclaimable usage and `free` the limit minus the
+/// whole usage, unless the host values are smaller. The other fields are always the host values
+/// from `/proc/meminfo`.
+/// If no cgroup has a limit or they cannot be read, the host values are returned unchanged.
+pub fn virtual_memory_effective() -> Result<VirtualMemory> {
+	let mut virtual_memory = virtual_memory()?;
+
+	let cgroup = match Cgroup::current() {
+		Ok(cgroup) => cgroup,
+		Err(_) => return Ok(virtual_memory),
+	};
+	// the usage is read from the cgroup with the smallest limit, which may be an ancestor
+	let (cgroup, limit) = match cgroup
+		.ancestors()
+		.filter_map(|cgroup| {
+			let limit = cgroup.memory_max().ok()??;
+
+			Some((cgroup, limit))
+		})
+		// a limit of 0 is ignored, it would make `percent` NaN
+		.filter(|(_, limit)| *limit > 0 && *limit < virtual_memory.total)
+		.min_by_key(|(_, limit)| *limit)
+	{
+		Some(limit) => limit,
+		None => return Ok(virtual_memory),
+	};
+	let usage = match cgroup.memory_current() {
+		Ok(usage) => usage,
+		Err(_) => return Ok(virtual_memory),
+	};
+	// named `total_inactive_file` in v1 to include descendants
+	let inactive_file = cgroup
+		.memory_stat()
+		.ok()
+		.and_then(|stat| {
+			stat.get("total_inactive_file")
+				.or_else(|| stat.get("inactive_file"))
+				.copied()
+		})
+		.unwrap_or_default();
+	let working_set = usage.saturating_sub(inactive_file).min(limit);
+
+	virtual_memory.total = limit;
+	virtual_memory.available = virtual_memory.available.min(limit - working_set);
+	virtual_memory.used = virtual_memory.used.min(working_set);
+	virtual_memory.free = virtual_memory.free.min(limit.saturating_sub(usage));
+	virtual_memory.percent =
+		(((limit as f64 - virtual_memory.available as f64) / limit as f64) * 100.0) as f32;
+
+	Ok(virtual_memory)
+}
+
+#[cfg(test)]
+mod unit_tests {
+	use super::*;
+
+	#[test]
+	fn test_virtual_memory_effective() {
+		let host = virtual_memory().unwrap();
+		let effective = virtual_memory_effective().unwrap();
+
+		assert!(effective.total() <= host.total());
+		assert!(effective.available() <= effective.total());
+	}
+}
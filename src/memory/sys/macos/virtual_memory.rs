@@ -62,3 +62,10 @@ pub fn virtual_memory() -> Result<VirtualMemory> {
 		wired,
 	})
 }
+
+/// New function, not in Python psutil.
+///
+/// Same as `virtual_memory()`, since there are no cgroups on macOS.
+pub fn virtual_memory_effective() -> Result<VirtualMemory> {
+	virtual_memory()
+}
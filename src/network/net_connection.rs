@@ -1,46 +1,73 @@
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::common::TcpConnectionStatus;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+
+use crate::common::{AddressFamily, SocketType, TcpConnectionStatus};
 use crate::{Fd, Pid};
 
+/// Address of one end of a connection.
+#[cfg_attr(feature = "serde", serde(crate = "renamed_serde"))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ConnectionAddress {
+	/// An IPv4 or IPv6 address and port.
+	Inet(SocketAddr),
+
+	/// The path of a unix socket. Unnamed sockets have an empty path and abstract sockets start
+	/// with `@`.
+	Unix(PathBuf),
+}
+
 #[cfg_attr(feature = "serde", serde(crate = "renamed_serde"))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub struct NetConnection {}
+#[derive(Clone, Debug)]
+pub struct NetConnection {
+	pub(crate) fd: Option<Fd>,
+	pub(crate) family: AddressFamily,
+	pub(crate) address_type: SocketType,
+	pub(crate) local_addr: ConnectionAddress,
+	pub(crate) remote_addr: Option<ConnectionAddress>,
+	pub(crate) status: Option<TcpConnectionStatus>,
+	pub(crate) pid: Option<Pid>,
+
+	#[cfg(target_os = "linux")]
+	pub(crate) inode: u64,
+}
 
 impl NetConnection {
+	/// The socket file descriptor, if it is owned by a process we can see.
 	pub fn fd(&self) -> Option<Fd> {
-		todo!()
+		self.fd
 	}
 
-	// TODO: return type
-	pub fn family(&self) {
-		todo!()
+	pub fn family(&self) -> AddressFamily {
+		self.family
 	}
 
-	// TODO: return type
 	/// Renamed from `type` in Python psutil.
-	pub fn address_type(&self) {
-		todo!()
+	pub fn address_type(&self) -> SocketType {
+		self.address_type
 	}
 
-	// TODO: return type
 	/// Renamed from `laddr` in Python psutil.
-	pub fn local_addr(&self) {
-		todo!()
+	pub fn local_addr(&self) -> &ConnectionAddress {
+		&self.local_addr
 	}
 
-	// TODO: return type
 	/// Renamed from `raddr` in Python psutil.
-	pub fn remote_addr(&self) {
-		todo!()
+	/// Returns `None` if the socket is not connected.
+	pub fn remote_addr(&self) -> Option<&ConnectionAddress> {
+		self.remote_addr.as_ref()
 	}
 
+	/// Only set for TCP sockets.
 	pub fn status(&self) -> Option<TcpConnectionStatus> {
-		todo!()
+		self.status
 	}
 
 	pub fn pid(&self) -> Option<Pid> {
-		todo!()
+		self.pid
 	}
 }
@@ -1,31 +1,148 @@
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+use std::fmt;
 use std::net::IpAddr;
 
+use crate::common::AddressFamily;
+
+/// New struct, not in Python psutil.
+///
+/// A link-layer address, usually a 6 byte MAC address.
+#[cfg_attr(feature = "serde", serde(crate = "renamed_serde"))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MacAddress(pub(crate) Vec<u8>);
+
+impl MacAddress {
+	pub fn bytes(&self) -> &[u8] {
+		&self.0
+	}
+}
+
+/// Formatted as colon separated hex bytes, such as `00:1a:2b:3c:4d:5e`.
+impl fmt::Display for MacAddress {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let bytes = self
+			.0
+			.iter()
+			.map(|byte| format!("{:02x}", byte))
+			.collect::<Vec<_>>();
+
+		f.write_str(&bytes.join(":"))
+	}
+}
+
+/// New enum, not in Python psutil.
+///
+/// An address of a network interface, Python psutil uses strings.
+#[cfg_attr(feature = "serde", serde(crate = "renamed_serde"))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum NetIfAddress {
+	Ip(IpAddr),
+	Mac(MacAddress),
+}
+
+impl NetIfAddress {
+	pub fn ip(&self) -> Option<IpAddr> {
+		match self {
+			NetIfAddress::Ip(ip) => Some(*ip),
+			NetIfAddress::Mac(_) => None,
+		}
+	}
+
+	pub fn mac(&self) -> Option<&MacAddress> {
+		match self {
+			NetIfAddress::Ip(_) => None,
+			NetIfAddress::Mac(mac) => Some(mac),
+		}
+	}
+}
+
+impl fmt::Display for NetIfAddress {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			NetIfAddress::Ip(ip) => ip.fmt(f),
+			NetIfAddress::Mac(mac) => mac.fmt(f),
+		}
+	}
+}
+
 #[cfg_attr(feature = "serde", serde(crate = "renamed_serde"))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub struct NetIfAddr {}
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NetIfAddr {
+	pub(crate) family: AddressFamily,
+	pub(crate) address: NetIfAddress,
+	pub(crate) netmask: Option<NetIfAddress>,
+	pub(crate) broadcast: Option<NetIfAddress>,
+	pub(crate) ptp: Option<NetIfAddress>,
+	pub(crate) scope_id: Option<u32>,
+}
 
 impl NetIfAddr {
-	// TODO: return type
-	pub fn family(&self) {
-		todo!()
+	/// `Inet`, `Inet6` or `Link`.
+	pub fn family(&self) -> AddressFamily {
+		self.family
+	}
+
+	/// An IP address, or a MAC address for the `Link` family.
+	pub fn address(&self) -> &NetIfAddress {
+		&self.address
 	}
 
-	pub fn address(&self) -> IpAddr {
-		todo!()
+	pub fn netmask(&self) -> Option<&NetIfAddress> {
+		self.netmask.as_ref()
 	}
 
-	pub fn netmask(&self) -> Option<IpAddr> {
-		todo!()
+	/// Only set if the interface supports broadcast, in which case `ptp` is `None`.
+	pub fn broadcast(&self) -> Option<&NetIfAddress> {
+		self.broadcast.as_ref()
 	}
 
-	pub fn broadcast(&self) -> Option<IpAddr> {
-		todo!()
+	/// The destination address of a point to point interface, in which case `broadcast` is
+	/// `None`.
+	pub fn ptp(&self) -> Option<&NetIfAddress> {
+		self.ptp.as_ref()
 	}
 
-	pub fn ptp(&self) -> Option<IpAddr> {
-		todo!()
+	/// New method, not in Python psutil.
+	/// The scope ID of an IPv6 address, such as the interface index for link-local addresses.
+	pub fn scope_id(&self) -> Option<u32> {
+		self.scope_id
+	}
+
+	/// New method, not in Python psutil.
+	/// The number of leading ones in the netmask of an IP address, such as 24 for `255.255.255.0`.
+	pub fn prefix_len(&self) -> Option<u8> {
+		let ones = match self.netmask.as_ref()?.ip()? {
+			IpAddr::V4(netmask) => u32::from(netmask).leading_ones(),
+			IpAddr::V6(netmask) => u128::from(netmask).leading_ones(),
+		};
+
+		Some(ones as u8)
+	}
+}
+
+#[cfg(test)]
+mod unit_tests {
+	use super::*;
+
+	#[test]
+	fn test_net_if_addr() {
+		let addr = NetIfAddr {
+			family: AddressFamily::Inet,
+			address: NetIfAddress::Ip("192.168.1.2".parse().unwrap()),
+			netmask: Some(NetIfAddress::Ip("255.255.254.0".parse().unwrap())),
+			broadcast: Some(NetIfAddress::Ip("192.168.1.255".parse().unwrap())),
+			ptp: None,
+			scope_id: None,
+		};
+		assert_eq!(addr.prefix_len(), Some(23));
+
+		let mac = NetIfAddress::Mac(MacAddress(vec![0, 0x1a, 0x2b, 0x3c, 0x4d, 0x5e]));
+		assert_eq!(mac.to_string(), "00:1a:2b:3c:4d:5e");
+		assert_eq!(mac.ip(), None);
 	}
 }
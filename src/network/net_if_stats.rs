@@ -1,34 +1,200 @@
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+use std::fmt;
+
+use nix::libc;
+
 use crate::Bytes;
 
 #[cfg_attr(feature = "serde", serde(crate = "renamed_serde"))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum Duplex {
 	Full,
 	Half,
 	Unknown,
 }
 
+/// New struct, not in Python psutil.
+///
+/// The `IFF_*` flags of a network interface, see `netdevice(7)`. Combine them with `|`.
+/// Python psutil uses a comma separated string, which is what `Display` produces.
 #[cfg_attr(feature = "serde", serde(crate = "renamed_serde"))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub struct NetIfStats {}
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct NetIfFlags(pub(crate) u32);
+
+impl NetIfFlags {
+	pub const UP: NetIfFlags = NetIfFlags(libc::IFF_UP as u32);
+	pub const BROADCAST: NetIfFlags = NetIfFlags(libc::IFF_BROADCAST as u32);
+	pub const DEBUG: NetIfFlags = NetIfFlags(libc::IFF_DEBUG as u32);
+	pub const LOOPBACK: NetIfFlags = NetIfFlags(libc::IFF_LOOPBACK as u32);
+	pub const POINTOPOINT: NetIfFlags = NetIfFlags(libc::IFF_POINTOPOINT as u32);
+	pub const NOTRAILERS: NetIfFlags = NetIfFlags(libc::IFF_NOTRAILERS as u32);
+	pub const RUNNING: NetIfFlags = NetIfFlags(libc::IFF_RUNNING as u32);
+	pub const NOARP: NetIfFlags = NetIfFlags(libc::IFF_NOARP as u32);
+	pub const PROMISC: NetIfFlags = NetIfFlags(libc::IFF_PROMISC as u32);
+	pub const ALLMULTI: NetIfFlags = NetIfFlags(libc::IFF_ALLMULTI as u32);
+	pub const MULTICAST: NetIfFlags = NetIfFlags(libc::IFF_MULTICAST as u32);
+	/// Linux only.
+	#[cfg(target_os = "linux")]
+	pub const MASTER: NetIfFlags = NetIfFlags(libc::IFF_MASTER as u32);
+	/// Linux only.
+	#[cfg(target_os = "linux")]
+	pub const SLAVE: NetIfFlags = NetIfFlags(libc::IFF_SLAVE as u32);
+	/// Linux only.
+	#[cfg(target_os = "linux")]
+	pub const PORTSEL: NetIfFlags = NetIfFlags(libc::IFF_PORTSEL as u32);
+	/// Linux only.
+	#[cfg(target_os = "linux")]
+	pub const AUTOMEDIA: NetIfFlags = NetIfFlags(libc::IFF_AUTOMEDIA as u32);
+	/// Linux only.
+	#[cfg(target_os = "linux")]
+	pub const DYNAMIC: NetIfFlags = NetIfFlags(libc::IFF_DYNAMIC as u32);
+	/// Linux only. The carrier is up.
+	#[cfg(target_os = "linux")]
+	pub const LOWER_UP: NetIfFlags = NetIfFlags(libc::IFF_LOWER_UP as u32);
+	/// Linux only.
+	#[cfg(target_os = "linux")]
+	pub const DORMANT: NetIfFlags = NetIfFlags(libc::IFF_DORMANT as u32);
+
+	/// Ordered by value, with the names used by Python psutil.
+	const NAMES: &'static [(NetIfFlags, &'static str)] = &[
+		(NetIfFlags::UP, "up"),
+		(NetIfFlags::BROADCAST, "broadcast"),
+		(NetIfFlags::DEBUG, "debug"),
+		(NetIfFlags::LOOPBACK, "loopback"),
+		(NetIfFlags::POINTOPOINT, "pointopoint"),
+		(NetIfFlags::NOTRAILERS, "notrailers"),
+		(NetIfFlags::RUNNING, "running"),
+		(NetIfFlags::NOARP, "noarp"),
+		(NetIfFlags::PROMISC, "promisc"),
+		(NetIfFlags::ALLMULTI, "allmulti"),
+		#[cfg(target_os = "linux")]
+		(NetIfFlags::MASTER, "master"),
+		#[cfg(target_os = "linux")]
+		(NetIfFlags::SLAVE, "slave"),
+		(NetIfFlags::MULTICAST, "multicast"),
+		#[cfg(target_os = "linux")]
+		(NetIfFlags::PORTSEL, "portsel"),
+		#[cfg(target_os = "linux")]
+		(NetIfFlags::AUTOMEDIA, "automedia"),
+		#[cfg(target_os = "linux")]
+		(NetIfFlags::DYNAMIC, "dynamic"),
+		#[cfg(target_os = "linux")]
+		(NetIfFlags::LOWER_UP, "lower_up"),
+		#[cfg(target_os = "linux")]
+		(NetIfFlags::DORMANT, "dormant"),
+	];
+
+	pub fn from_bits(bits: u32) -> NetIfFlags {
+		NetIfFlags(bits)
+	}
+
+	pub fn bits(self) -> u32 {
+		self.0
+	}
+
+	/// Returns `true` if all flags of `other` are set.
+	pub fn contains(self, other: NetIfFlags) -> bool {
+		self.0 & other.0 == other.0
+	}
+
+	pub fn is_empty(self) -> bool {
+		self.0 == 0
+	}
+
+	/// The names of the known flags that are set, such as `["up", "running"]`.
+	pub fn names(self) -> Vec<&'static str> {
+		NetIfFlags::NAMES
+			.iter()
+			.filter(|(flag, _)| self.contains(*flag))
+			.map(|(_, name)| *name)
+			.collect()
+	}
+}
+
+impl std::ops::BitOr for NetIfFlags {
+	type Output = NetIfFlags;
+
+	fn bitor(self, other: NetIfFlags) -> NetIfFlags {
+		NetIfFlags(self.0 | other.0)
+	}
+}
+
+impl fmt::Display for NetIfFlags {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str(&self.names().join(","))
+	}
+}
+
+#[cfg_attr(feature = "serde", serde(crate = "renamed_serde"))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NetIfStats {
+	pub(crate) is_up: bool,
+	pub(crate) duplex: Duplex,
+	pub(crate) speed: u64,
+	pub(crate) mtu: Bytes,
+	pub(crate) flags: NetIfFlags,
+}
 
 impl NetIfStats {
+	/// Whether the interface is up and running.
 	pub fn is_up(&self) -> bool {
-		todo!()
+		self.is_up
 	}
 
 	pub fn duplex(&self) -> Duplex {
-		todo!()
+		self.duplex
 	}
 
-	pub fn speed(&self) -> Bytes {
-		todo!()
+	/// The speed in megabits per second, or 0 if it cannot be determined, e.g. for `lo`.
+	pub fn speed(&self) -> u64 {
+		self.speed
 	}
 
 	pub fn mtu(&self) -> Bytes {
-		todo!()
+		self.mtu
+	}
+
+	pub fn flags(&self) -> NetIfFlags {
+		self.flags
+	}
+
+	/// New method, not in Python psutil.
+	pub fn is_running(&self) -> bool {
+		self.flags.contains(NetIfFlags::RUNNING)
+	}
+
+	/// New method, not in Python psutil.
+	pub fn is_loopback(&self) -> bool {
+		self.flags.contains(NetIfFlags::LOOPBACK)
+	}
+
+	/// New method, not in Python psutil.
+	pub fn is_promisc(&self) -> bool {
+		self.flags.contains(NetIfFlags::PROMISC)
+	}
+
+	/// New method, not in Python psutil.
+	pub fn is_multicast(&self) -> bool {
+		self.flags.contains(NetIfFlags::MULTICAST)
+	}
+}
+
+#[cfg(test)]
+mod unit_tests {
+	use super::*;
+
+	#[test]
+	fn test_net_if_flags() {
+		let flags = NetIfFlags::UP | NetIfFlags::RUNNING | NetIfFlags::MULTICAST;
+
+		assert!(flags.contains(NetIfFlags::UP | NetIfFlags::RUNNING));
+		assert!(!flags.contains(NetIfFlags::PROMISC));
+		assert_eq!(flags.to_string(), "up,running,multicast");
+		assert!(NetIfFlags::default().is_empty());
 	}
 }
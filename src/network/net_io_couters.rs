@@ -2,6 +2,7 @@
 use serde::{Deserialize, Serialize};
 
 use std::collections::HashMap;
+use std::time::Instant;
 
 use derive_more::{Add, Sub, Sum};
 
@@ -10,7 +11,7 @@ use crate::{Bytes, Count, Result};
 
 #[cfg_attr(feature = "serde", serde(crate = "renamed_serde"))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Clone, Debug, Add, Sum, Sub)]
+#[derive(Clone, Debug, Default, Add, Sum, Sub)]
 pub struct NetIoCounters {
 	pub(crate) bytes_sent: Bytes,
 	pub(crate) bytes_recv: Bytes,
@@ -20,6 +21,23 @@ pub struct NetIoCounters {
 	pub(crate) err_out: Count,
 	pub(crate) drop_in: Count,
 	pub(crate) drop_out: Count,
+
+	#[cfg(target_os = "linux")]
+	pub(crate) fifo_in: Count,
+	#[cfg(target_os = "linux")]
+	pub(crate) frame_in: Count,
+	#[cfg(target_os = "linux")]
+	pub(crate) compressed_in: Count,
+	#[cfg(target_os = "linux")]
+	pub(crate) multicast_in: Count,
+	#[cfg(target_os = "linux")]
+	pub(crate) fifo_out: Count,
+	#[cfg(target_os = "linux")]
+	pub(crate) colls_out: Count,
+	#[cfg(target_os = "linux")]
+	pub(crate) carrier_out: Count,
+	#[cfg(target_os = "linux")]
+	pub(crate) compressed_out: Count,
 }
 
 impl NetIoCounters {
@@ -68,11 +86,18 @@ impl NetIoCounters {
 	}
 }
 
+/// A decrease is only treated as a wrap of a 32-bit counter, as used by some drivers and 32-bit
+/// kernels, if the previous value was close to `u32::MAX`. Otherwise the counter was reset, e.g.
+/// because the interface was recreated, since 64-bit counters never wrap in practice.
+/// A reset of a 64-bit counter whose previous value was between 2^31 and 2^32 is still counted as
+/// a wrap, overcounting it by up to 2^31.
 fn nowrap(prev: u64, current: u64, corrected: u64) -> u64 {
 	if current >= prev {
 		corrected + (current - prev)
+	} else if prev > (u32::MAX / 2) as u64 && prev <= u32::MAX as u64 {
+		corrected + current + (u32::MAX as u64 - prev) + 1
 	} else {
-		corrected + current + ((u32::MAX as u64) - prev)
+		corrected + current
 	}
 }
 
@@ -98,6 +123,35 @@ fn nowrap_struct(
 		err_out: nowrap(prev.err_out, current.err_out, corrected.err_out),
 		drop_in: nowrap(prev.drop_in, current.drop_in, corrected.drop_in),
 		drop_out: nowrap(prev.drop_out, current.drop_out, corrected.drop_out),
+
+		#[cfg(target_os = "linux")]
+		fifo_in: nowrap(prev.fifo_in, current.fifo_in, corrected.fifo_in),
+		#[cfg(target_os = "linux")]
+		frame_in: nowrap(prev.frame_in, current.frame_in, corrected.frame_in),
+		#[cfg(target_os = "linux")]
+		compressed_in: nowrap(
+			prev.compressed_in,
+			current.compressed_in,
+			corrected.compressed_in,
+		),
+		#[cfg(target_os = "linux")]
+		multicast_in: nowrap(
+			prev.multicast_in,
+			current.multicast_in,
+			corrected.multicast_in,
+		),
+		#[cfg(target_os = "linux")]
+		fifo_out: nowrap(prev.fifo_out, current.fifo_out, corrected.fifo_out),
+		#[cfg(target_os = "linux")]
+		colls_out: nowrap(prev.colls_out, current.colls_out, corrected.colls_out),
+		#[cfg(target_os = "linux")]
+		carrier_out: nowrap(prev.carrier_out, current.carrier_out, corrected.carrier_out),
+		#[cfg(target_os = "linux")]
+		compressed_out: nowrap(
+			prev.compressed_out,
+			current.compressed_out,
+			corrected.compressed_out,
+		),
 	}
 }
 
@@ -124,11 +178,86 @@ fn fix_io_counter_overflow(
 		.collect()
 }
 
+/// New struct, not in Python psutil.
+///
+/// Per second rates of network I/O, see `NetIoCountersCollector::net_io_rates`.
+#[cfg_attr(feature = "serde", serde(crate = "renamed_serde"))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Default, PartialEq, Add, Sum)]
+pub struct NetIoRates {
+	pub(crate) bytes_sent: f64,
+	pub(crate) bytes_recv: f64,
+	pub(crate) packets_sent: f64,
+	pub(crate) packets_recv: f64,
+	pub(crate) err_in: f64,
+	pub(crate) err_out: f64,
+	pub(crate) drop_in: f64,
+	pub(crate) drop_out: f64,
+}
+
+impl NetIoRates {
+	fn new(prev: &NetIoCounters, current: &NetIoCounters, seconds: f64) -> NetIoRates {
+		let rate = |prev: u64, current: u64| current.saturating_sub(prev) as f64 / seconds;
+
+		NetIoRates {
+			bytes_sent: rate(prev.bytes_sent, current.bytes_sent),
+			bytes_recv: rate(prev.bytes_recv, current.bytes_recv),
+			packets_sent: rate(prev.packets_sent, current.packets_sent),
+			packets_recv: rate(prev.packets_recv, current.packets_recv),
+			err_in: rate(prev.err_in, current.err_in),
+			err_out: rate(prev.err_out, current.err_out),
+			drop_in: rate(prev.drop_in, current.drop_in),
+			drop_out: rate(prev.drop_out, current.drop_out),
+		}
+	}
+
+	/// Number of bytes sent per second.
+	pub fn bytes_sent(&self) -> f64 {
+		self.bytes_sent
+	}
+
+	/// Number of bytes received per second.
+	pub fn bytes_recv(&self) -> f64 {
+		self.bytes_recv
+	}
+
+	/// Number of packets sent per second.
+	pub fn packets_sent(&self) -> f64 {
+		self.packets_sent
+	}
+
+	/// Number of packets received per second.
+	pub fn packets_recv(&self) -> f64 {
+		self.packets_recv
+	}
+
+	/// Number of errors while receiving per second.
+	pub fn err_in(&self) -> f64 {
+		self.err_in
+	}
+
+	/// Number of errors while sending per second.
+	pub fn err_out(&self) -> f64 {
+		self.err_out
+	}
+
+	/// Number of incoming packets which were dropped per second.
+	pub fn drop_in(&self) -> f64 {
+		self.drop_in
+	}
+
+	/// Number of outgoing packets which were dropped per second.
+	pub fn drop_out(&self) -> f64 {
+		self.drop_out
+	}
+}
+
 /// Used to persist data between calls to detect data overflow by the kernel and fix the result.
 #[derive(Debug, Clone, Default)]
 pub struct NetIoCountersCollector {
 	prev_net_io_counters_pernic: Option<HashMap<String, NetIoCounters>>,
 	corrected_net_io_counters_pernic: Option<HashMap<String, NetIoCounters>>,
+	rates_net_io_counters_pernic: Option<(Instant, HashMap<String, NetIoCounters>)>,
 }
 
 impl NetIoCountersCollector {
@@ -154,4 +283,91 @@ impl NetIoCountersCollector {
 
 		Ok(corrected_counters)
 	}
+
+	/// New method, not in Python psutil.
+	///
+	/// Returns the rates summed over all network interfaces since the last time this or
+	/// `net_io_rates_pernic` was called. See `net_io_rates_pernic`.
+	pub fn net_io_rates(&mut self) -> Result<NetIoRates> {
+		let sum = self.net_io_rates_pernic()?.into_values().sum();
+
+		Ok(sum)
+	}
+
+	/// New method, not in Python psutil.
+	///
+	/// Returns the rates of each network interface since the last time this or `net_io_rates`
+	/// was called. The rates are 0 on the first call and for interfaces that were added since the
+	/// last call.
+	pub fn net_io_rates_pernic(&mut self) -> Result<HashMap<String, NetIoRates>> {
+		let io_counters = self.net_io_counters_pernic()?;
+		let now = Instant::now();
+
+		let rates = match &self.rates_net_io_counters_pernic {
+			Some((prev_time, prev)) => {
+				let seconds = now.duration_since(*prev_time).as_secs_f64();
+
+				io_counters
+					.iter()
+					.map(|(name, current)| {
+						let rates = match prev.get(name) {
+							Some(prev) if seconds > 0.0 => NetIoRates::new(prev, current, seconds),
+							_ => NetIoRates::default(),
+						};
+
+						(name.clone(), rates)
+					})
+					.collect()
+			}
+			None => io_counters
+				.keys()
+				.map(|name| (name.clone(), NetIoRates::default()))
+				.collect(),
+		};
+
+		self.rates_net_io_counters_pernic = Some((now, io_counters));
+
+		Ok(rates)
+	}
+}
+
+#[cfg(test)]
+mod unit_tests {
+	use super::*;
+
+	#[test]
+	fn test_nowrap() {
+		assert_eq!(nowrap(10, 15, 100), 105);
+		// 32-bit counter wrapped
+		assert_eq!(nowrap(u32::MAX as u64 - 1, 3, 100), 105);
+		// counter was reset
+		assert_eq!(nowrap(u32::MAX as u64 + 10, 3, 100), 103);
+		assert_eq!(nowrap(1000, 3, 100), 103);
+	}
+
+	#[test]
+	fn test_net_io_rates() {
+		let prev = NetIoCounters {
+			bytes_sent: 1000,
+			bytes_recv: 2000,
+			packets_recv: 10,
+			..Default::default()
+		};
+		let current = NetIoCounters {
+			bytes_sent: 3000,
+			bytes_recv: 2500,
+			packets_recv: 14,
+			..Default::default()
+		};
+		let rates = NetIoRates::new(&prev, &current, 2.0);
+
+		assert_eq!(rates.bytes_sent(), 1000.0);
+		assert_eq!(rates.bytes_recv(), 250.0);
+		assert_eq!(rates.packets_recv(), 2.0);
+		assert_eq!(rates.err_in(), 0.0);
+
+		let mut collector = NetIoCountersCollector::default();
+		let rates = collector.net_io_rates_pernic().unwrap();
+		assert!(rates.values().all(|rates| *rates == NetIoRates::default()));
+	}
 }
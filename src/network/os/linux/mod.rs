@@ -0,0 +1,7 @@
+mod net_if_statistics;
+mod net_io_counters;
+mod net_protocol_stats;
+
+pub use net_if_statistics::*;
+pub use net_io_counters::*;
+pub use net_protocol_stats::*;
@@ -0,0 +1,161 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::Count;
+
+/// New struct, not in Python psutil.
+///
+/// The counters in `/sys/class/net/<interface>/statistics/`, which break down the errors of
+/// `NetIoCounters`. Not every driver fills in every counter.
+#[cfg_attr(feature = "serde", serde(crate = "renamed_serde"))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NetIfStatistics {
+	pub(crate) rx_bytes: Count,
+	pub(crate) rx_packets: Count,
+	pub(crate) rx_errors: Count,
+	pub(crate) rx_dropped: Count,
+	pub(crate) rx_length_errors: Count,
+	pub(crate) rx_over_errors: Count,
+	pub(crate) rx_crc_errors: Count,
+	pub(crate) rx_frame_errors: Count,
+	pub(crate) rx_fifo_errors: Count,
+	pub(crate) rx_missed_errors: Count,
+	pub(crate) rx_compressed: Count,
+	pub(crate) multicast: Count,
+	pub(crate) tx_bytes: Count,
+	pub(crate) tx_packets: Count,
+	pub(crate) tx_errors: Count,
+	pub(crate) tx_dropped: Count,
+	pub(crate) tx_aborted_errors: Count,
+	pub(crate) tx_carrier_errors: Count,
+	pub(crate) tx_fifo_errors: Count,
+	pub(crate) tx_heartbeat_errors: Count,
+	pub(crate) tx_window_errors: Count,
+	pub(crate) tx_compressed: Count,
+	pub(crate) collisions: Count,
+	pub(crate) rx_nohandler: Option<Count>,
+}
+
+impl NetIfStatistics {
+	/// Number of bytes received.
+	pub fn rx_bytes(&self) -> Count {
+		self.rx_bytes
+	}
+
+	/// Number of packets received.
+	pub fn rx_packets(&self) -> Count {
+		self.rx_packets
+	}
+
+	/// Total number of errors while receiving.
+	pub fn rx_errors(&self) -> Count {
+		self.rx_errors
+	}
+
+	/// Number of incoming packets which were dropped.
+	pub fn rx_dropped(&self) -> Count {
+		self.rx_dropped
+	}
+
+	/// Number of packets received with an invalid length.
+	pub fn rx_length_errors(&self) -> Count {
+		self.rx_length_errors
+	}
+
+	/// Number of packets received that overflowed the receive ring buffer.
+	pub fn rx_over_errors(&self) -> Count {
+		self.rx_over_errors
+	}
+
+	/// Number of packets received with a CRC error.
+	pub fn rx_crc_errors(&self) -> Count {
+		self.rx_crc_errors
+	}
+
+	/// Number of packets received with a frame alignment error.
+	pub fn rx_frame_errors(&self) -> Count {
+		self.rx_frame_errors
+	}
+
+	/// Number of receive FIFO overruns.
+	pub fn rx_fifo_errors(&self) -> Count {
+		self.rx_fifo_errors
+	}
+
+	/// Number of packets missed by the host, e.g. due to a full receive queue.
+	pub fn rx_missed_errors(&self) -> Count {
+		self.rx_missed_errors
+	}
+
+	/// Number of compressed packets received.
+	pub fn rx_compressed(&self) -> Count {
+		self.rx_compressed
+	}
+
+	/// Number of multicast packets received.
+	pub fn multicast(&self) -> Count {
+		self.multicast
+	}
+
+	/// Number of bytes sent.
+	pub fn tx_bytes(&self) -> Count {
+		self.tx_bytes
+	}
+
+	/// Number of packets sent.
+	pub fn tx_packets(&self) -> Count {
+		self.tx_packets
+	}
+
+	/// Total number of errors while sending.
+	pub fn tx_errors(&self) -> Count {
+		self.tx_errors
+	}
+
+	/// Number of outgoing packets which were dropped.
+	pub fn tx_dropped(&self) -> Count {
+		self.tx_dropped
+	}
+
+	/// Number of packets whose transmission was aborted.
+	pub fn tx_aborted_errors(&self) -> Count {
+		self.tx_aborted_errors
+	}
+
+	/// Number of packets that could not be sent due to carrier errors.
+	pub fn tx_carrier_errors(&self) -> Count {
+		self.tx_carrier_errors
+	}
+
+	/// Number of transmit FIFO underruns.
+	pub fn tx_fifo_errors(&self) -> Count {
+		self.tx_fifo_errors
+	}
+
+	/// Number of heartbeat errors of old half duplex Ethernet.
+	pub fn tx_heartbeat_errors(&self) -> Count {
+		self.tx_heartbeat_errors
+	}
+
+	/// Number of late collisions of half duplex Ethernet.
+	pub fn tx_window_errors(&self) -> Count {
+		self.tx_window_errors
+	}
+
+	/// Number of compressed packets sent.
+	pub fn tx_compressed(&self) -> Count {
+		self.tx_compressed
+	}
+
+	/// Number of collisions of half duplex Ethernet.
+	pub fn collisions(&self) -> Count {
+		self.collisions
+	}
+
+	/// Number of packets received that were dropped because no protocol handled them.
+	/// Since Linux 4.6.
+	pub fn rx_nohandler(&self) -> Option<Count> {
+		self.rx_nohandler
+	}
+}
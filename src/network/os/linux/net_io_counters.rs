@@ -0,0 +1,64 @@
+use crate::network::NetIoCounters;
+use crate::Count;
+
+/// The columns of `/proc/net/dev` that are not in `NetIoCounters`.
+/// See `network::net_if_statistics` for a more detailed breakdown of the errors.
+pub trait NetIoCountersExt {
+	/// Number of receive FIFO buffer errors.
+	fn fifo_in(&self) -> Count;
+
+	/// Number of packets received with a frame alignment error.
+	fn frame_in(&self) -> Count;
+
+	/// Number of compressed packets received.
+	fn compressed_in(&self) -> Count;
+
+	/// Number of multicast packets received.
+	fn multicast_in(&self) -> Count;
+
+	/// Number of transmit FIFO buffer errors.
+	fn fifo_out(&self) -> Count;
+
+	/// Number of collisions while sending.
+	fn colls_out(&self) -> Count;
+
+	/// Number of carrier losses while sending.
+	fn carrier_out(&self) -> Count;
+
+	/// Number of compressed packets sent.
+	fn compressed_out(&self) -> Count;
+}
+
+impl NetIoCountersExt for NetIoCounters {
+	fn fifo_in(&self) -> Count {
+		self.fifo_in
+	}
+
+	fn frame_in(&self) -> Count {
+		self.frame_in
+	}
+
+	fn compressed_in(&self) -> Count {
+		self.compressed_in
+	}
+
+	fn multicast_in(&self) -> Count {
+		self.multicast_in
+	}
+
+	fn fifo_out(&self) -> Count {
+		self.fifo_out
+	}
+
+	fn colls_out(&self) -> Count {
+		self.colls_out
+	}
+
+	fn carrier_out(&self) -> Count {
+		self.carrier_out
+	}
+
+	fn compressed_out(&self) -> Count {
+		self.compressed_out
+	}
+}
@@ -0,0 +1,395 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::network::net_protocol_stats;
+use crate::{Count, Result};
+
+/// Defines a struct of counters with a getter for each field, and a `delta` method that returns
+/// the increase of every counter since `prev`.
+macro_rules! counters {
+	(
+		$(#[$struct_meta:meta])*
+		pub struct $name:ident {
+			$($(#[$field_meta:meta])* $field:ident,)*
+		}
+	) => {
+		$(#[$struct_meta])*
+		#[cfg_attr(feature = "serde", serde(crate = "renamed_serde"))]
+		#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+		#[derive(Clone, Debug, Default, PartialEq, Eq)]
+		pub struct $name {
+			$(pub(crate) $field: Count,)*
+		}
+
+		impl $name {
+			/// Counters that decreased, e.g. due to a wrap on 32-bit kernels, are 0.
+			fn delta(&self, prev: &$name) -> $name {
+				$name {
+					$($field: self.$field.saturating_sub(prev.$field),)*
+				}
+			}
+
+			$(
+				$(#[$field_meta])*
+				pub fn $field(&self) -> Count {
+					self.$field
+				}
+			)*
+		}
+	};
+}
+
+counters! {
+	/// New struct, not in Python psutil.
+	///
+	/// Counters of the `Ip` line of `/proc/net/snmp`, or the `Ip6` lines of `/proc/net/snmp6`.
+	pub struct IpStats {
+		/// Number of datagrams received, including those with errors.
+		in_receives,
+		/// Number of datagrams discarded due to header errors.
+		in_hdr_errors,
+		/// Number of datagrams discarded due to an invalid destination address.
+		in_addr_errors,
+		/// Number of datagrams forwarded.
+		forw_datagrams,
+		/// Number of datagrams discarded due to an unknown protocol.
+		in_unknown_protos,
+		/// Number of datagrams received that were discarded, e.g. due to a lack of buffer space.
+		in_discards,
+		/// Number of datagrams delivered to higher level protocols.
+		in_delivers,
+		/// Number of datagrams supplied by higher level protocols for sending.
+		out_requests,
+		/// Number of datagrams to send that were discarded, e.g. due to a lack of buffer space.
+		out_discards,
+		/// Number of datagrams discarded because no route was found.
+		out_no_routes,
+		/// Number of reassembly failures.
+		reasm_fails,
+		/// Number of datagrams discarded because they could not be fragmented.
+		frag_fails,
+	}
+}
+
+counters! {
+	/// New struct, not in Python psutil.
+	///
+	/// Counters of the `Icmp` line of `/proc/net/snmp`, or the `Icmp6` lines of `/proc/net/snmp6`.
+	pub struct IcmpStats {
+		/// Number of messages received, including those with errors.
+		in_msgs,
+		/// Number of messages received with errors.
+		in_errors,
+		/// Number of messages received with a checksum error.
+		in_csum_errors,
+		/// Number of destination unreachable messages received.
+		in_dest_unreachs,
+		/// Number of time exceeded messages received.
+		in_time_excds,
+		/// Number of echo requests received.
+		in_echos,
+		/// Number of echo replies received.
+		in_echo_reps,
+		/// Number of messages sent, including those with errors.
+		out_msgs,
+		/// Number of messages that could not be sent.
+		out_errors,
+		/// Number of destination unreachable messages sent.
+		out_dest_unreachs,
+		/// Number of time exceeded messages sent.
+		out_time_excds,
+		/// Number of echo requests sent.
+		out_echos,
+		/// Number of echo replies sent.
+		out_echo_reps,
+	}
+}
+
+counters! {
+	/// New struct, not in Python psutil.
+	///
+	/// Counters of the `Tcp` line of `/proc/net/snmp`, which also covers TCP over IPv6.
+	pub struct TcpStats {
+		/// Number of connections opened by connecting.
+		active_opens,
+		/// Number of connections opened by accepting.
+		passive_opens,
+		/// Number of failed connection attempts.
+		attempt_fails,
+		/// Number of established connections that were reset.
+		estab_resets,
+		/// Number of currently established connections. Not a counter, so it is kept as is by
+		/// `NetProtocolStatsCollector`.
+		curr_estab,
+		/// Number of segments received, including those with errors.
+		in_segs,
+		/// Number of segments sent, excluding retransmissions.
+		out_segs,
+		/// Number of segments retransmitted.
+		retrans_segs,
+		/// Number of segments received with errors.
+		in_errs,
+		/// Number of resets sent.
+		out_rsts,
+		/// Number of segments received with a checksum error.
+		in_csum_errors,
+	}
+}
+
+counters! {
+	/// New struct, not in Python psutil.
+	///
+	/// Counters of the `Udp` line of `/proc/net/snmp`, or the `Udp6` lines of `/proc/net/snmp6`.
+	pub struct UdpStats {
+		/// Number of datagrams delivered to sockets.
+		in_datagrams,
+		/// Number of datagrams received for a port without a socket.
+		no_ports,
+		/// Number of datagrams received that could not be delivered for other reasons.
+		in_errors,
+		/// Number of datagrams sent.
+		out_datagrams,
+		/// Number of datagrams dropped due to a full receive buffer.
+		rcvbuf_errors,
+		/// Number of datagrams dropped due to a full send buffer.
+		sndbuf_errors,
+		/// Number of datagrams received with a checksum error.
+		in_csum_errors,
+		/// Number of broadcast and multicast datagrams ignored.
+		ignored_multi,
+		/// Number of datagrams dropped due to memory pressure.
+		mem_errors,
+	}
+}
+
+counters! {
+	/// New struct, not in Python psutil.
+	///
+	/// Counters of the `TcpExt` line of `/proc/net/netstat`, named after the kernel names
+	/// without the `TCP` prefix.
+	pub struct TcpExtStats {
+		/// Number of SYN cookies sent, usually due to a full SYN queue.
+		syncookies_sent,
+		/// Number of valid SYN cookies received.
+		syncookies_recv,
+		/// Number of invalid SYN cookies received.
+		syncookies_failed,
+		/// Number of resets received for connections in `SYN_RECV`.
+		embryonic_rsts,
+		/// Number of times the receive queue was pruned due to memory pressure.
+		prune_called,
+		/// Number of connections dropped due to a full accept queue.
+		listen_overflows,
+		/// Number of connections dropped while listening, including `listen_overflows`.
+		listen_drops,
+		/// Number of delayed acks sent.
+		delayed_acks,
+		/// Number of retransmissions that were lost.
+		lost_retransmit,
+		/// Number of fast retransmissions.
+		fast_retrans,
+		/// Number of retransmissions in slow start.
+		slow_start_retrans,
+		/// Number of retransmission timeouts.
+		timeouts,
+		/// Number of tail loss probes sent.
+		loss_probes,
+		/// Number of retransmission timeouts that turned out to be spurious.
+		spurious_rtos,
+		/// Number of SYN and SYN-ACK retransmissions.
+		syn_retrans,
+		/// Number of retransmissions that failed, e.g. due to a full send queue.
+		retrans_fail,
+		/// Number of segments with new data sent, the denominator of the retransmission ratio.
+		orig_data_sent,
+		/// Number of connections reset due to unexpected data.
+		abort_on_data,
+		/// Number of connections reset because they were closed with unread data.
+		abort_on_close,
+		/// Number of connections reset due to memory pressure.
+		abort_on_memory,
+		/// Number of connections reset after too many retransmissions.
+		abort_on_timeout,
+		/// Number of connections reset after the linger timeout.
+		abort_on_linger,
+		/// Number of resets that could not be sent.
+		abort_failed,
+		/// Number of times TCP entered memory pressure.
+		memory_pressures,
+		/// Number of segments dropped due to a full socket backlog.
+		backlog_drop,
+		/// Number of SYN cookies sent due to a full SYN queue.
+		req_q_full_do_cookies,
+		/// Number of SYNs dropped due to a full SYN queue and disabled SYN cookies.
+		req_q_full_drop,
+		/// Number of out of order segments dropped due to memory limits.
+		ofo_drop,
+		/// Number of segments dropped due to a full receive queue.
+		rcv_q_drop,
+		/// Number of segments dropped due to a zero receive window.
+		zero_window_drop,
+		/// Number of `TIME_WAIT` sockets that could not be allocated.
+		time_wait_overflow,
+	}
+}
+
+/// New struct, not in Python psutil.
+///
+/// The IPv6 counters are `None` if IPv6 is disabled.
+#[cfg_attr(feature = "serde", serde(crate = "renamed_serde"))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NetProtocolStats {
+	pub(crate) ip: IpStats,
+	pub(crate) ip6: Option<IpStats>,
+	pub(crate) icmp: IcmpStats,
+	pub(crate) icmp6: Option<IcmpStats>,
+	pub(crate) tcp: TcpStats,
+	pub(crate) tcp_ext: TcpExtStats,
+	pub(crate) udp: UdpStats,
+	pub(crate) udp6: Option<UdpStats>,
+}
+
+impl NetProtocolStats {
+	fn delta(&self, prev: &NetProtocolStats) -> NetProtocolStats {
+		NetProtocolStats {
+			ip: self.ip.delta(&prev.ip),
+			ip6: self
+				.ip6
+				.as_ref()
+				.zip(prev.ip6.as_ref())
+				.map(|(current, prev)| current.delta(prev)),
+			icmp: self.icmp.delta(&prev.icmp),
+			icmp6: self
+				.icmp6
+				.as_ref()
+				.zip(prev.icmp6.as_ref())
+				.map(|(current, prev)| current.delta(prev)),
+			tcp: TcpStats {
+				curr_estab: self.tcp.curr_estab,
+				..self.tcp.delta(&prev.tcp)
+			},
+			tcp_ext: self.tcp_ext.delta(&prev.tcp_ext),
+			udp: self.udp.delta(&prev.udp),
+			udp6: self
+				.udp6
+				.as_ref()
+				.zip(prev.udp6.as_ref())
+				.map(|(current, prev)| current.delta(prev)),
+		}
+	}
+
+	pub fn ip(&self) -> &IpStats {
+		&self.ip
+	}
+
+	pub fn ip6(&self) -> Option<&IpStats> {
+		self.ip6.as_ref()
+	}
+
+	pub fn icmp(&self) -> &IcmpStats {
+		&self.icmp
+	}
+
+	pub fn icmp6(&self) -> Option<&IcmpStats> {
+		self.icmp6.as_ref()
+	}
+
+	/// Covers both IPv4 and IPv6.
+	pub fn tcp(&self) -> &TcpStats {
+		&self.tcp
+	}
+
+	/// Covers both IPv4 and IPv6.
+	pub fn tcp_ext(&self) -> &TcpExtStats {
+		&self.tcp_ext
+	}
+
+	pub fn udp(&self) -> &UdpStats {
+		&self.udp
+	}
+
+	pub fn udp6(&self) -> Option<&UdpStats> {
+		self.udp6.as_ref()
+	}
+}
+
+/// New struct, not in Python psutil.
+///
+/// Used to persist data between calls to get the protocol counters since the previous call.
+#[derive(Clone, Debug, Default)]
+pub struct NetProtocolStatsCollector {
+	prev_net_protocol_stats: Option<NetProtocolStats>,
+}
+
+impl NetProtocolStatsCollector {
+	/// Returns the increase of every counter since the last time this was called, which is 0 on
+	/// the first call. Gauges such as `TcpStats::curr_estab` are returned as is.
+	pub fn net_protocol_stats(&mut self) -> Result<NetProtocolStats> {
+		let current = net_protocol_stats()?;
+		let delta = current.delta(self.prev_net_protocol_stats.as_ref().unwrap_or(&current));
+
+		self.prev_net_protocol_stats = Some(current);
+
+		Ok(delta)
+	}
+}
+
+#[cfg(test)]
+mod unit_tests {
+	use super::*;
+
+	#[test]
+	fn test_delta() {
+		let prev = NetProtocolStats {
+			tcp: TcpStats {
+				retrans_segs: 10,
+				curr_estab: 5,
+				..Default::default()
+			},
+			tcp_ext: TcpExtStats {
+				listen_drops: 3,
+				..Default::default()
+			},
+			udp6: Some(UdpStats {
+				in_errors: 7,
+				..Default::default()
+			}),
+			..Default::default()
+		};
+		let current = NetProtocolStats {
+			tcp: TcpStats {
+				retrans_segs: 25,
+				curr_estab: 2,
+				..Default::default()
+			},
+			tcp_ext: TcpExtStats {
+				listen_drops: 4,
+				..Default::default()
+			},
+			udp6: Some(UdpStats {
+				// wrapped
+				in_errors: 1,
+				..Default::default()
+			}),
+			..Default::default()
+		};
+		let delta = current.delta(&prev);
+
+		assert_eq!(delta.tcp().retrans_segs(), 15);
+		// a gauge
+		assert_eq!(delta.tcp().curr_estab(), 2);
+		assert_eq!(delta.tcp_ext().listen_drops(), 1);
+		assert_eq!(delta.udp6().unwrap().in_errors(), 0);
+		assert_eq!(delta.ip6(), None);
+	}
+
+	#[test]
+	fn test_net_protocol_stats_collector() {
+		let mut collector = NetProtocolStatsCollector::default();
+		let delta = collector.net_protocol_stats().unwrap();
+
+		assert_eq!(delta.tcp().retrans_segs(), 0);
+		assert!(collector.net_protocol_stats().is_ok());
+	}
+}
@@ -1,10 +1,16 @@
 mod net_connections;
 mod net_if_addrs;
+mod net_if_statistics;
 mod net_if_stats;
 mod net_io_counters;
+mod net_protocol_stats;
+mod sock_diag;
 
 pub use net_connections::*;
 pub use net_if_addrs::*;
+pub use net_if_statistics::*;
 pub use net_if_stats::*;
 #[allow(unused_imports)]
 pub use net_io_counters::*;
+pub use net_protocol_stats::*;
+pub use sock_diag::*;
@@ -1,10 +1,387 @@
-use crate::common::NetConnectionType;
-use crate::network::NetConnection;
+use std::collections::HashMap;
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::path::{Path, PathBuf};
 
-pub fn net_connections() -> Vec<NetConnection> {
-	todo!()
+use crate::common::{AddressFamily, NetConnectionType, SocketType, TcpConnectionStatus};
+use crate::network::{ConnectionAddress, NetConnection};
+use crate::{read_dir, read_file, read_link, Error, Fd, Pid, Result};
+
+const PROC_NET: &str = "/proc/net";
+
+/// A table in `/proc/net` and the kind of sockets it lists.
+pub(super) struct ProcNetTable {
+	name: &'static str,
+	pub(super) family: AddressFamily,
+	pub(super) address_type: SocketType,
+}
+
+const TCP4: ProcNetTable = ProcNetTable {
+	name: "tcp",
+	family: AddressFamily::Inet,
+	address_type: SocketType::Stream,
+};
+const TCP6: ProcNetTable = ProcNetTable {
+	name: "tcp6",
+	family: AddressFamily::Inet6,
+	address_type: SocketType::Stream,
+};
+const UDP4: ProcNetTable = ProcNetTable {
+	name: "udp",
+	family: AddressFamily::Inet,
+	address_type: SocketType::Datagram,
+};
+const UDP6: ProcNetTable = ProcNetTable {
+	name: "udp6",
+	family: AddressFamily::Inet6,
+	address_type: SocketType::Datagram,
+};
+// The socket type of unix sockets is read from the table itself.
+const UNIX: ProcNetTable = ProcNetTable {
+	name: "unix",
+	family: AddressFamily::Unix,
+	address_type: SocketType::Stream,
+};
+
+pub(super) fn tables(type_: NetConnectionType) -> &'static [ProcNetTable] {
+	match type_ {
+		NetConnectionType::Inet => &[TCP4, TCP6, UDP4, UDP6],
+		NetConnectionType::Inet4 => &[TCP4, UDP4],
+		NetConnectionType::Inet6 => &[TCP6, UDP6],
+		NetConnectionType::Tcp => &[TCP4, TCP6],
+		NetConnectionType::Tcp4 => &[TCP4],
+		NetConnectionType::Tcp6 => &[TCP6],
+		NetConnectionType::Udp => &[UDP4, UDP6],
+		NetConnectionType::Udp4 => &[UDP4],
+		NetConnectionType::Udp6 => &[UDP6],
+		NetConnectionType::Unix => &[UNIX],
+		NetConnectionType::All => &[TCP4, TCP6, UDP4, UDP6, UNIX],
+	}
+}
+
+/// See `include/net/tcp_states.h` in the kernel sources.
+pub(super) fn tcp_status(state: u8) -> Option<TcpConnectionStatus> {
+	match state {
+		0x01 => Some(TcpConnectionStatus::Established),
+		0x02 => Some(TcpConnectionStatus::SynSent),
+		// 0x0C is TCP_NEW_SYN_RECV
+		0x03 | 0x0C => Some(TcpConnectionStatus::SynRecv),
+		0x04 => Some(TcpConnectionStatus::FinWait1),
+		0x05 => Some(TcpConnectionStatus::FinWait2),
+		0x06 => Some(TcpConnectionStatus::TimeWait),
+		0x07 => Some(TcpConnectionStatus::Close),
+		0x08 => Some(TcpConnectionStatus::CloseWait),
+		0x09 => Some(TcpConnectionStatus::LastAck),
+		0x0A => Some(TcpConnectionStatus::Listen),
+		0x0B => Some(TcpConnectionStatus::Closing),
+		_ => None,
+	}
+}
+
+/// Addresses are printed as the hex value of each 32 bit word in host byte order,
+/// e.g. `0100007F:0050` is `127.0.0.1:80` on little endian machines.
+fn parse_inet_address(s: &str, family: AddressFamily) -> Option<SocketAddr> {
+	let (ip, port) = s.split_once(':')?;
+	let port = u16::from_str_radix(port, 16).ok()?;
+
+	let word = |i: usize| -> Option<[u8; 4]> {
+		let word = ip.get(i * 8..(i + 1) * 8)?;
+		Some(u32::from_str_radix(word, 16).ok()?.to_ne_bytes())
+	};
+
+	let ip = match family {
+		AddressFamily::Inet if ip.len() == 8 => IpAddr::V4(Ipv4Addr::from(word(0)?)),
+		AddressFamily::Inet6 if ip.len() == 32 => {
+			let mut octets = [0; 16];
+			for i in 0..4 {
+				octets[i * 4..(i + 1) * 4].copy_from_slice(&word(i)?);
+			}
+			IpAddr::V6(Ipv6Addr::from(octets))
+		}
+		_ => return None,
+	};
+
+	Some(SocketAddr::new(ip, port))
 }
 
-pub fn net_connections_with_type(_type: NetConnectionType) -> Vec<NetConnection> {
-	todo!()
+fn parse_inet_line(line: &str, path: &Path, table: &ProcNetTable) -> Result<NetConnection> {
+	let missing_data = || Error::MissingData {
+		path: path.into(),
+		contents: line.to_string(),
+	};
+
+	// Example: `0: 0100007F:0277 00000000:0000 0A 00000000:00000000 00:00000000 00000000 0 0 21839 1 ...`
+	let fields = match line.split_whitespace().collect::<Vec<_>>() {
+		fields if fields.len() >= 10 => Ok(fields),
+		_ => Err(missing_data()),
+	}?;
+
+	let parse_int = |err| Error::ParseInt {
+		path: path.into(),
+		contents: line.to_string(),
+		source: err,
+	};
+
+	let local_addr = parse_inet_address(fields[1], table.family).ok_or_else(missing_data)?;
+	let remote_addr = parse_inet_address(fields[2], table.family).ok_or_else(missing_data)?;
+	// unconnected sockets have a remote address of `0.0.0.0:0` or `[::]:0`
+	let remote_addr = if remote_addr.ip().is_unspecified() && remote_addr.port() == 0 {
+		None
+	} else {
+		Some(ConnectionAddress::Inet(remote_addr))
+	};
+
+	let status = if table.address_type == SocketType::Stream {
+		tcp_status(u8::from_str_radix(fields[3], 16).map_err(parse_int)?)
+	} else {
+		None
+	};
+
+	let inode = fields[9].parse().map_err(parse_int)?;
+
+	Ok(NetConnection {
+		fd: None,
+		family: table.family,
+		address_type: table.address_type,
+		local_addr: ConnectionAddress::Inet(local_addr),
+		remote_addr,
+		status,
+		pid: None,
+		inode,
+	})
+}
+
+fn parse_unix_line(line: &str, path: &Path) -> Result<Option<NetConnection>> {
+	// Example: `0000000000000000: 00000002 00000000 00010000 0001 01 21943 /run/dbus/system_bus_socket`
+	let fields = match line.split_whitespace().collect::<Vec<_>>() {
+		fields if fields.len() >= 7 => Ok(fields),
+		_ => Err(Error::MissingData {
+			path: path.into(),
+			contents: line.to_string(),
+		}),
+	}?;
+
+	let parse_int = |err| Error::ParseInt {
+		path: path.into(),
+		contents: line.to_string(),
+		source: err,
+	};
+
+	// See `include/linux/net.h` in the kernel sources.
+	let address_type = match u32::from_str_radix(fields[4], 16).map_err(parse_int)? {
+		1 => SocketType::Stream,
+		2 => SocketType::Datagram,
+		5 => SocketType::SeqPacket,
+		_ => return Ok(None),
+	};
+
+	let inode = fields[6].parse().map_err(parse_int)?;
+
+	// paths can contain spaces
+	let local_addr = ConnectionAddress::Unix(PathBuf::from(fields[7..].join(" ")));
+
+	Ok(Some(NetConnection {
+		fd: None,
+		family: AddressFamily::Unix,
+		address_type,
+		local_addr,
+		remote_addr: None,
+		status: None,
+		pid: None,
+		inode,
+	}))
+}
+
+/// Parses the socket tables in `root`, which is either `/proc/net` or `/proc/[pid]/net`.
+/// The `fd` and `pid` of the returned connections are not set.
+pub(crate) fn procfs_net_connections(
+	root: &Path,
+	type_: NetConnectionType,
+) -> Result<Vec<NetConnection>> {
+	let mut connections = Vec::new();
+
+	for table in tables(type_) {
+		let path = root.join(table.name);
+		let contents = match read_file(&path) {
+			Ok(contents) => contents,
+			// e.g. the tables for IPv6 are missing if it is disabled
+			Err(e) if is_not_found(&e) => continue,
+			Err(e) => return Err(e),
+		};
+
+		// skip the header
+		for line in contents.lines().skip(1) {
+			if table.family == AddressFamily::Unix {
+				connections.extend(parse_unix_line(line, &path)?);
+			} else {
+				connections.push(parse_inet_line(line, &path, table)?);
+			}
+		}
+	}
+
+	Ok(connections)
+}
+
+fn is_not_found(e: &Error) -> bool {
+	matches!(e, Error::ReadFile { source, .. } if source.kind() == io::ErrorKind::NotFound)
+}
+
+/// Maps the inode of every socket opened by a process to its file descriptor.
+pub(crate) fn socket_inodes(pid: Pid) -> Result<HashMap<u64, Fd>> {
+	let mut inodes = HashMap::new();
+
+	for entry in read_dir(PathBuf::from("/proc").join(pid.to_string()).join("fd"))? {
+		let path = entry.path();
+
+		let fd = match path
+			.file_name()
+			.expect("directory entries should always contain a file name")
+			.to_string_lossy()
+			.parse::<Fd>()
+		{
+			Ok(fd) => fd,
+			Err(_) => continue,
+		};
+		let link = match read_link(&path) {
+			Ok(link) => link,
+			// the file descriptor was closed in the meantime
+			Err(e) if is_not_found(&e) => continue,
+			Err(e) => return Err(e),
+		};
+
+		// Example: `socket:[21839]`
+		if let Some(inode) = link
+			.to_str()
+			.and_then(|link| link.strip_prefix("socket:["))
+			.and_then(|link| link.strip_suffix(']'))
+			.and_then(|inode| inode.parse().ok())
+		{
+			inodes.insert(inode, fd);
+		}
+	}
+
+	Ok(inodes)
+}
+
+/// Sets the `pid` and `fd` of each connection by scanning the fd table of every process once.
+/// Processes we are not allowed to inspect or that exit in the meantime are skipped, so their
+/// connections are left without a `pid`.
+pub(super) fn set_socket_owners(connections: &mut [NetConnection]) -> Result<()> {
+	let mut owners: HashMap<u64, (Pid, Fd)> = HashMap::new();
+
+	for entry in read_dir("/proc")? {
+		let pid = match entry.file_name().to_string_lossy().parse::<Pid>() {
+			Ok(pid) => pid,
+			Err(_) => continue,
+		};
+
+		if let Ok(inodes) = socket_inodes(pid) {
+			owners.extend(inodes.into_iter().map(|(inode, fd)| (inode, (pid, fd))));
+		}
+	}
+
+	for connection in connections.iter_mut() {
+		if let Some((pid, fd)) = owners.get(&connection.inode) {
+			connection.pid = Some(*pid);
+			connection.fd = Some(*fd);
+		}
+	}
+
+	Ok(())
+}
+
+/// Returns the inet sockets of the system.
+pub fn net_connections() -> Result<Vec<NetConnection>> {
+	net_connections_with_type(NetConnectionType::Inet)
+}
+
+/// Requires root to attribute sockets to processes owned by other users.
+pub fn net_connections_with_type(type_: NetConnectionType) -> Result<Vec<NetConnection>> {
+	let mut connections = procfs_net_connections(Path::new(PROC_NET), type_)?;
+	set_socket_owners(&mut connections)?;
+
+	Ok(connections)
+}
+
+#[cfg(test)]
+mod unit_tests {
+	use super::*;
+
+	#[test]
+	fn test_parse_inet_address() {
+		let input = if cfg!(target_endian = "little") {
+			"0100007F:0277"
+		} else {
+			"7F000001:0277"
+		};
+		assert_eq!(
+			parse_inet_address(input, AddressFamily::Inet),
+			Some("127.0.0.1:631".parse().unwrap())
+		);
+
+		let input = if cfg!(target_endian = "little") {
+			"00000000000000000000000001000000:0016"
+		} else {
+			"00000000000000000000000000000001:0016"
+		};
+		assert_eq!(
+			parse_inet_address(input, AddressFamily::Inet6),
+			Some("[::1]:22".parse().unwrap())
+		);
+
+		assert_eq!(parse_inet_address("0100007F", AddressFamily::Inet), None);
+		assert_eq!(
+			parse_inet_address("0100007F:0277", AddressFamily::Inet6),
+			None
+		);
+	}
+
+	#[test]
+	fn test_parse_inet_line() {
+		let line = "   0: 0100007F:0277 00000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 21839 1 0000000000000000 100 0 0 10 0";
+		let connection = parse_inet_line(line, Path::new("tcp"), &TCP4).unwrap();
+		assert_eq!(connection.family(), AddressFamily::Inet);
+		assert_eq!(connection.address_type(), SocketType::Stream);
+		assert_eq!(connection.status(), Some(TcpConnectionStatus::Listen));
+		assert_eq!(connection.remote_addr(), None);
+		assert_eq!(connection.inode, 21839);
+
+		let connection = parse_inet_line(line, Path::new("udp"), &UDP4).unwrap();
+		assert_eq!(connection.address_type(), SocketType::Datagram);
+		assert_eq!(connection.status(), None);
+	}
+
+	#[test]
+	fn test_parse_unix_line() {
+		let line =
+			"0000000000000000: 00000002 00000000 00010000 0001 01 21943 /run/dbus/system bus";
+		let connection = parse_unix_line(line, Path::new("unix")).unwrap().unwrap();
+		assert_eq!(connection.address_type(), SocketType::Stream);
+		assert_eq!(
+			connection.local_addr(),
+			&ConnectionAddress::Unix("/run/dbus/system bus".into())
+		);
+		assert_eq!(connection.inode, 21943);
+
+		let line = "0000000000000000: 00000002 00000000 00000000 0002 01 31337";
+		let connection = parse_unix_line(line, Path::new("unix")).unwrap().unwrap();
+		assert_eq!(connection.address_type(), SocketType::Datagram);
+		assert_eq!(connection.local_addr(), &ConnectionAddress::Unix("".into()));
+	}
+
+	#[test]
+	fn test_net_connections() {
+		let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+		let local_addr = ConnectionAddress::Inet(listener.local_addr().unwrap());
+
+		let connection = net_connections_with_type(NetConnectionType::Tcp4)
+			.unwrap()
+			.into_iter()
+			.find(|connection| connection.local_addr() == &local_addr)
+			.unwrap();
+		assert_eq!(connection.pid(), Some(std::process::id()));
+		assert_eq!(connection.status(), Some(TcpConnectionStatus::Listen));
+
+		net_connections_with_type(NetConnectionType::All).unwrap();
+	}
 }
@@ -1,8 +1,87 @@
 use std::collections::HashMap;
-use std::io;
+use std::net::IpAddr;
 
-use crate::network::NetIfAddr;
+use nix::ifaddrs::getifaddrs;
+use nix::sys::socket::SockaddrStorage;
 
-pub fn net_if_addrs() -> io::Result<HashMap<String, Vec<NetIfAddr>>> {
-	todo!()
+use crate::common::AddressFamily;
+use crate::network::{MacAddress, NetIfAddr, NetIfAddress};
+use crate::Result;
+
+fn to_address(storage: &SockaddrStorage) -> Option<(AddressFamily, NetIfAddress)> {
+	if let Some(addr) = storage.as_sockaddr_in() {
+		Some((AddressFamily::Inet, NetIfAddress::Ip(IpAddr::V4(addr.ip()))))
+	} else if let Some(addr) = storage.as_sockaddr_in6() {
+		Some((
+			AddressFamily::Inet6,
+			NetIfAddress::Ip(IpAddr::V6(addr.ip())),
+		))
+	} else {
+		let addr = storage.as_link_addr()?;
+		let sll_addr = addr.as_ref().sll_addr;
+		let len = addr.halen().min(sll_addr.len());
+
+		Some((
+			AddressFamily::Link,
+			NetIfAddress::Mac(MacAddress(sll_addr[..len].to_vec())),
+		))
+	}
+}
+
+/// Addresses of other families, such as those of CAN interfaces, are left out.
+pub fn net_if_addrs() -> Result<HashMap<String, Vec<NetIfAddr>>> {
+	let mut net_if_addrs: HashMap<String, Vec<NetIfAddr>> = HashMap::new();
+
+	for interface in getifaddrs()? {
+		let (family, address) = match interface.address.as_ref().and_then(to_address) {
+			Some(address) => address,
+			None => continue,
+		};
+		let to_same_family = |storage: &Option<SockaddrStorage>| {
+			storage
+				.as_ref()
+				.and_then(to_address)
+				.filter(|(other, _)| *other == family)
+				.map(|(_, address)| address)
+		};
+
+		let net_if_addr = NetIfAddr {
+			family,
+			netmask: to_same_family(&interface.netmask),
+			broadcast: to_same_family(&interface.broadcast),
+			ptp: to_same_family(&interface.destination),
+			scope_id: interface
+				.address
+				.as_ref()
+				.and_then(|storage| storage.as_sockaddr_in6())
+				.map(|addr| addr.scope_id()),
+			address,
+		};
+
+		net_if_addrs
+			.entry(interface.interface_name)
+			.or_default()
+			.push(net_if_addr);
+	}
+
+	Ok(net_if_addrs)
+}
+
+#[cfg(test)]
+mod unit_tests {
+	use super::*;
+
+	#[test]
+	fn test_net_if_addrs() {
+		let net_if_addrs = net_if_addrs().unwrap();
+		let lo = &net_if_addrs["lo"];
+
+		let inet = lo
+			.iter()
+			.find(|addr| addr.family() == AddressFamily::Inet)
+			.unwrap();
+		assert_eq!(inet.address().to_string(), "127.0.0.1");
+		assert_eq!(inet.prefix_len(), Some(8));
+		assert!(lo.iter().any(|addr| addr.family() == AddressFamily::Link));
+	}
 }
@@ -0,0 +1,89 @@
+// https://www.kernel.org/doc/Documentation/ABI/testing/sysfs-class-net-statistics
+
+use std::collections::HashMap;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::network::os::linux::NetIfStatistics;
+use crate::{read_dir, read_file, Count, Error, Result};
+
+const SYS_CLASS_NET: &str = "/sys/class/net";
+
+fn parse_statistic(path: &Path) -> Result<Count> {
+	let contents = read_file(path)?;
+
+	contents.trim().parse().map_err(|err| Error::ParseInt {
+		path: path.into(),
+		contents,
+		source: err,
+	})
+}
+
+fn read_statistics(dir: &Path) -> Result<NetIfStatistics> {
+	let read = |name: &str| parse_statistic(&dir.join(name));
+
+	Ok(NetIfStatistics {
+		rx_bytes: read("rx_bytes")?,
+		rx_packets: read("rx_packets")?,
+		rx_errors: read("rx_errors")?,
+		rx_dropped: read("rx_dropped")?,
+		rx_length_errors: read("rx_length_errors")?,
+		rx_over_errors: read("rx_over_errors")?,
+		rx_crc_errors: read("rx_crc_errors")?,
+		rx_frame_errors: read("rx_frame_errors")?,
+		rx_fifo_errors: read("rx_fifo_errors")?,
+		rx_missed_errors: read("rx_missed_errors")?,
+		rx_compressed: read("rx_compressed")?,
+		multicast: read("multicast")?,
+		tx_bytes: read("tx_bytes")?,
+		tx_packets: read("tx_packets")?,
+		tx_errors: read("tx_errors")?,
+		tx_dropped: read("tx_dropped")?,
+		tx_aborted_errors: read("tx_aborted_errors")?,
+		tx_carrier_errors: read("tx_carrier_errors")?,
+		tx_fifo_errors: read("tx_fifo_errors")?,
+		tx_heartbeat_errors: read("tx_heartbeat_errors")?,
+		tx_window_errors: read("tx_window_errors")?,
+		tx_compressed: read("tx_compressed")?,
+		collisions: read("collisions")?,
+		rx_nohandler: read("rx_nohandler").ok(),
+	})
+}
+
+/// New function, not in Python psutil.
+///
+/// Reads the statistics of every network interface. Interfaces that are removed while reading
+/// are left out.
+pub fn net_if_statistics() -> Result<HashMap<String, NetIfStatistics>> {
+	let mut net_if_statistics = HashMap::new();
+
+	for entry in read_dir(SYS_CLASS_NET)? {
+		let name = entry.file_name().to_string_lossy().into_owned();
+		let dir = PathBuf::from(SYS_CLASS_NET).join(&name).join("statistics");
+
+		match read_statistics(&dir) {
+			Ok(statistics) => {
+				net_if_statistics.insert(name, statistics);
+			}
+			Err(Error::ReadFile { source, .. }) if source.kind() == io::ErrorKind::NotFound => {}
+			Err(e) => return Err(e),
+		}
+	}
+
+	Ok(net_if_statistics)
+}
+
+#[cfg(test)]
+mod unit_tests {
+	use super::*;
+	use crate::network::net_io_counters_pernic;
+
+	#[test]
+	fn test_net_if_statistics() {
+		let counters = net_io_counters_pernic().unwrap();
+		let lo = &net_if_statistics().unwrap()["lo"];
+
+		assert!(lo.rx_bytes() >= counters["lo"].bytes_recv());
+		assert_eq!(lo.collisions(), 0);
+	}
+}
@@ -1,8 +1,265 @@
+// https://www.kernel.org/doc/Documentation/ABI/testing/sysfs-class-net
+// include/uapi/linux/ethtool.h
+
 use std::collections::HashMap;
 use std::io;
+use std::mem;
+use std::os::unix::io::{AsRawFd, FromRawFd, OwnedFd};
+use std::path::PathBuf;
+
+use nix::libc;
+use nix::net::if_::if_nameindex;
+
+use crate::network::{Duplex, NetIfFlags, NetIfStats};
+use crate::{read_file, Error, Result};
+
+const ETHTOOL_GSET: u32 = 0x00000001;
+
+const DUPLEX_HALF: u8 = 0x00;
+const DUPLEX_FULL: u8 = 0x01;
+
+const SPEED_UNKNOWN: u32 = u32::MAX;
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+struct ethtool_cmd {
+	cmd: u32,
+	supported: u32,
+	advertising: u32,
+	speed: u16,
+	duplex: u8,
+	port: u8,
+	phy_address: u8,
+	transceiver: u8,
+	autoneg: u8,
+	mdio_support: u8,
+	maxtxpkt: u32,
+	maxrxpkt: u32,
+	speed_hi: u16,
+	eth_tp_mdix: u8,
+	eth_tp_mdix_ctrl: u8,
+	lp_advertising: u32,
+	reserved: [u32; 2],
+}
+
+fn sysfs_path(name: &str, file: &str) -> PathBuf {
+	PathBuf::from("/sys/class/net").join(name).join(file)
+}
+
+fn read_sysfs(name: &str, file: &str) -> Result<String> {
+	Ok(read_file(sysfs_path(name, file))?.trim().to_string())
+}
+
+fn parse_sysfs<T: std::str::FromStr>(name: &str, file: &str) -> Result<T> {
+	let contents = read_sysfs(name, file)?;
+
+	contents.parse().map_err(|_| Error::MissingData {
+		path: sysfs_path(name, file),
+		contents,
+	})
+}
+
+fn parse_duplex(contents: &str) -> Duplex {
+	match contents {
+		"full" => Duplex::Full,
+		"half" => Duplex::Half,
+		_ => Duplex::Unknown,
+	}
+}
+
+/// The flags in sysfs leave out the ones derived from the operational state, which are added
+/// back as in `dev_get_flags`. Interfaces without a link state, such as `lo`, report an unknown
+/// operational state and count as running.
+fn operstate_flags(flags: NetIfFlags, operstate: &str, carrier: bool) -> NetIfFlags {
+	if !flags.contains(NetIfFlags::UP) {
+		return flags;
+	}
+
+	let mut flags = flags;
+	if operstate == "up" || operstate == "unknown" {
+		flags = flags | NetIfFlags::RUNNING;
+	}
+	if operstate == "dormant" {
+		flags = flags | NetIfFlags::DORMANT;
+	}
+	if carrier {
+		flags = flags | NetIfFlags::LOWER_UP;
+	}
+
+	flags
+}
+
+fn sysfs_net_if_stats(name: &str) -> Result<NetIfStats> {
+	let flags_hex = read_sysfs(name, "flags")?;
+	let flags = u32::from_str_radix(flags_hex.trim_start_matches("0x"), 16).map_err(|err| {
+		Error::ParseInt {
+			path: sysfs_path(name, "flags"),
+			contents: flags_hex.clone(),
+			source: err,
+		}
+	})?;
+	let flags = operstate_flags(
+		NetIfFlags::from_bits(flags),
+		&read_sysfs(name, "operstate")?,
+		// reading fails with EINVAL for interfaces that are down
+		read_sysfs(name, "carrier").is_ok_and(|carrier| carrier == "1"),
+	);
+
+	Ok(NetIfStats {
+		is_up: flags.contains(NetIfFlags::UP | NetIfFlags::RUNNING),
+		// reading fails with EINVAL for interfaces that are down or have no link speed
+		duplex: read_sysfs(name, "duplex")
+			.map(|duplex| parse_duplex(&duplex))
+			.unwrap_or(Duplex::Unknown),
+		speed: parse_sysfs::<i64>(name, "speed")
+			.ok()
+			.filter(|speed| *speed > 0)
+			.unwrap_or(0) as u64,
+		mtu: parse_sysfs(name, "mtu")?,
+		flags,
+	})
+}
+
+fn ifreq(name: &str) -> io::Result<libc::ifreq> {
+	let mut ifreq: libc::ifreq = unsafe { mem::zeroed() };
+	if name.len() >= ifreq.ifr_name.len() {
+		return Err(io::Error::from_raw_os_error(libc::ENODEV));
+	}
+	for (dst, src) in ifreq.ifr_name.iter_mut().zip(name.bytes()) {
+		*dst = src as libc::c_char;
+	}
+
+	Ok(ifreq)
+}
+
+fn ioctl(socket: &OwnedFd, request: libc::c_ulong, ifreq: &mut libc::ifreq) -> io::Result<()> {
+	let result =
+		unsafe { libc::ioctl(socket.as_raw_fd(), request as _, ifreq as *mut libc::ifreq) };
+	if result < 0 {
+		return Err(io::Error::last_os_error());
+	}
+
+	Ok(())
+}
+
+/// Used if sysfs is not mounted, as in some containers. Leaves out `LOWER_UP` and `DORMANT`.
+fn ioctl_net_if_stats(socket: &OwnedFd, name: &str) -> io::Result<NetIfStats> {
+	let mut request = ifreq(name)?;
+	ioctl(socket, libc::SIOCGIFFLAGS, &mut request)?;
+	let flags = NetIfFlags::from_bits(unsafe { request.ifr_ifru.ifru_flags } as u16 as u32);
+
+	let mut request = ifreq(name)?;
+	ioctl(socket, libc::SIOCGIFMTU, &mut request)?;
+	let mtu = unsafe { request.ifr_ifru.ifru_mtu } as u64;
+
+	let mut cmd: ethtool_cmd = unsafe { mem::zeroed() };
+	cmd.cmd = ETHTOOL_GSET;
+	let mut request = ifreq(name)?;
+	request.ifr_ifru.ifru_data = &mut cmd as *mut ethtool_cmd as *mut libc::c_char;
+
+	// fails with EOPNOTSUPP for virtual interfaces
+	let (duplex, speed) = match ioctl(socket, libc::SIOCETHTOOL, &mut request) {
+		Ok(()) => {
+			let duplex = match cmd.duplex {
+				DUPLEX_HALF => Duplex::Half,
+				DUPLEX_FULL => Duplex::Full,
+				_ => Duplex::Unknown,
+			};
+			let speed = (cmd.speed_hi as u32) << 16 | cmd.speed as u32;
+			let speed = match speed {
+				SPEED_UNKNOWN | 0xffff => 0,
+				speed => speed as u64,
+			};
+
+			(duplex, speed)
+		}
+		Err(_) => (Duplex::Unknown, 0),
+	};
+
+	Ok(NetIfStats {
+		is_up: flags.contains(NetIfFlags::UP | NetIfFlags::RUNNING),
+		duplex,
+		speed,
+		mtu,
+		flags,
+	})
+}
+
+fn ioctl_socket() -> io::Result<OwnedFd> {
+	let fd = unsafe { libc::socket(libc::AF_INET, libc::SOCK_DGRAM | libc::SOCK_CLOEXEC, 0) };
+	if fd < 0 {
+		return Err(io::Error::last_os_error());
+	}
+
+	Ok(unsafe { OwnedFd::from_raw_fd(fd) })
+}
+
+/// Reads `/sys/class/net/<interface>/`, falling back to `SIOCGIFFLAGS`, `SIOCGIFMTU` and
+/// `SIOCETHTOOL` ioctls if it is not available.
+/// Interfaces that are removed while reading are left out.
+pub fn net_if_stats() -> Result<HashMap<String, NetIfStats>> {
+	let mut socket = None;
+	let mut net_if_stats = HashMap::new();
+
+	for interface in if_nameindex()?.iter() {
+		let name = interface.name().to_string_lossy().into_owned();
+
+		let stats = match sysfs_net_if_stats(&name) {
+			Ok(stats) => stats,
+			Err(_) => {
+				if socket.is_none() {
+					socket = Some(ioctl_socket()?);
+				}
+
+				match ioctl_net_if_stats(socket.as_ref().unwrap(), &name) {
+					Ok(stats) => stats,
+					Err(err) if err.raw_os_error() == Some(libc::ENODEV) => continue,
+					Err(err) => return Err(err.into()),
+				}
+			}
+		};
+
+		net_if_stats.insert(name, stats);
+	}
+
+	Ok(net_if_stats)
+}
+
+#[cfg(test)]
+mod unit_tests {
+	use super::*;
+
+	#[test]
+	fn test_operstate_flags() {
+		let up = NetIfFlags::UP | NetIfFlags::MULTICAST;
+
+		assert_eq!(
+			operstate_flags(up, "up", true),
+			up | NetIfFlags::RUNNING | NetIfFlags::LOWER_UP
+		);
+		assert_eq!(
+			operstate_flags(up, "unknown", false),
+			up | NetIfFlags::RUNNING
+		);
+		assert_eq!(operstate_flags(up, "down", false), up);
+		assert_eq!(
+			operstate_flags(NetIfFlags::MULTICAST, "down", true),
+			NetIfFlags::MULTICAST
+		);
+		assert_eq!(parse_duplex("half"), Duplex::Half);
+	}
 
-use crate::network::NetIfStats;
+	#[test]
+	fn test_net_if_stats() {
+		let lo = net_if_stats().unwrap()["lo"];
+		assert!(lo.is_loopback());
+		assert_eq!(lo.speed(), 0);
 
-pub fn net_if_stats() -> io::Result<HashMap<String, NetIfStats>> {
-	todo!()
+		let socket = ioctl_socket().unwrap();
+		let ioctl_lo = ioctl_net_if_stats(&socket, "lo").unwrap();
+		// `SIOCGIFFLAGS` only returns the lower 16 bits
+		assert_eq!(ioctl_lo.flags().bits(), lo.flags().bits() & 0xffff);
+		assert_eq!(ioctl_lo.mtu(), lo.mtu());
+		assert_eq!(ioctl_lo.is_up(), lo.is_up());
+	}
 }
@@ -35,6 +35,14 @@ impl FromStr for NetIoCounters {
 			err_out: parse(fields[11])?,
 			drop_in: parse(fields[4])?,
 			drop_out: parse(fields[12])?,
+			fifo_in: parse(fields[5])?,
+			frame_in: parse(fields[6])?,
+			compressed_in: parse(fields[7])?,
+			multicast_in: parse(fields[8])?,
+			fifo_out: parse(fields[13])?,
+			colls_out: parse(fields[14])?,
+			carrier_out: parse(fields[15])?,
+			compressed_out: parse(fields[16])?,
 		})
 	}
 }
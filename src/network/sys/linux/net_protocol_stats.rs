@@ -0,0 +1,297 @@
+// https://www.kernel.org/doc/html/latest/networking/snmp_counter.html
+
+use std::collections::HashMap;
+use std::io;
+
+use crate::network::os::linux::{
+	IcmpStats, IpStats, NetProtocolStats, TcpExtStats, TcpStats, UdpStats,
+};
+use crate::{read_file, Count, Error, Result};
+
+const PROC_NET_SNMP: &str = "/proc/net/snmp";
+const PROC_NET_SNMP6: &str = "/proc/net/snmp6";
+const PROC_NET_NETSTAT: &str = "/proc/net/netstat";
+
+/// The values of one protocol, keyed by their kernel name without the protocol prefix.
+type Table = HashMap<String, i64>;
+
+/// Returns the first value found, or 0 for counters that the kernel does not have yet.
+fn count(table: &Table, keys: &[&str]) -> Count {
+	keys.iter()
+		.find_map(|key| table.get(*key))
+		.map(|value| (*value).max(0) as Count)
+		.unwrap_or(0)
+}
+
+impl IpStats {
+	fn from_table(table: &Table) -> IpStats {
+		IpStats {
+			in_receives: count(table, &["InReceives"]),
+			in_hdr_errors: count(table, &["InHdrErrors"]),
+			in_addr_errors: count(table, &["InAddrErrors"]),
+			forw_datagrams: count(table, &["ForwDatagrams", "OutForwDatagrams"]),
+			in_unknown_protos: count(table, &["InUnknownProtos"]),
+			in_discards: count(table, &["InDiscards"]),
+			in_delivers: count(table, &["InDelivers"]),
+			out_requests: count(table, &["OutRequests"]),
+			out_discards: count(table, &["OutDiscards"]),
+			out_no_routes: count(table, &["OutNoRoutes"]),
+			reasm_fails: count(table, &["ReasmFails"]),
+			frag_fails: count(table, &["FragFails"]),
+		}
+	}
+}
+
+impl IcmpStats {
+	fn from_table(table: &Table) -> IcmpStats {
+		IcmpStats {
+			in_msgs: count(table, &["InMsgs"]),
+			in_errors: count(table, &["InErrors"]),
+			in_csum_errors: count(table, &["InCsumErrors"]),
+			in_dest_unreachs: count(table, &["InDestUnreachs"]),
+			in_time_excds: count(table, &["InTimeExcds"]),
+			in_echos: count(table, &["InEchos"]),
+			in_echo_reps: count(table, &["InEchoReps", "InEchoReplies"]),
+			out_msgs: count(table, &["OutMsgs"]),
+			out_errors: count(table, &["OutErrors"]),
+			out_dest_unreachs: count(table, &["OutDestUnreachs"]),
+			out_time_excds: count(table, &["OutTimeExcds"]),
+			out_echos: count(table, &["OutEchos"]),
+			out_echo_reps: count(table, &["OutEchoReps", "OutEchoReplies"]),
+		}
+	}
+}
+
+impl TcpStats {
+	fn from_table(table: &Table) -> TcpStats {
+		TcpStats {
+			active_opens: count(table, &["ActiveOpens"]),
+			passive_opens: count(table, &["PassiveOpens"]),
+			attempt_fails: count(table, &["AttemptFails"]),
+			estab_resets: count(table, &["EstabResets"]),
+			curr_estab: count(table, &["CurrEstab"]),
+			in_segs: count(table, &["InSegs"]),
+			out_segs: count(table, &["OutSegs"]),
+			retrans_segs: count(table, &["RetransSegs"]),
+			in_errs: count(table, &["InErrs"]),
+			out_rsts: count(table, &["OutRsts"]),
+			in_csum_errors: count(table, &["InCsumErrors"]),
+		}
+	}
+}
+
+impl UdpStats {
+	fn from_table(table: &Table) -> UdpStats {
+		UdpStats {
+			in_datagrams: count(table, &["InDatagrams"]),
+			no_ports: count(table, &["NoPorts"]),
+			in_errors: count(table, &["InErrors"]),
+			out_datagrams: count(table, &["OutDatagrams"]),
+			rcvbuf_errors: count(table, &["RcvbufErrors"]),
+			sndbuf_errors: count(table, &["SndbufErrors"]),
+			in_csum_errors: count(table, &["InCsumErrors"]),
+			ignored_multi: count(table, &["IgnoredMulti"]),
+			mem_errors: count(table, &["MemErrors"]),
+		}
+	}
+}
+
+impl TcpExtStats {
+	fn from_table(table: &Table) -> TcpExtStats {
+		TcpExtStats {
+			syncookies_sent: count(table, &["SyncookiesSent"]),
+			syncookies_recv: count(table, &["SyncookiesRecv"]),
+			syncookies_failed: count(table, &["SyncookiesFailed"]),
+			embryonic_rsts: count(table, &["EmbryonicRsts"]),
+			prune_called: count(table, &["PruneCalled"]),
+			listen_overflows: count(table, &["ListenOverflows"]),
+			listen_drops: count(table, &["ListenDrops"]),
+			delayed_acks: count(table, &["DelayedACKs"]),
+			lost_retransmit: count(table, &["TCPLostRetransmit"]),
+			fast_retrans: count(table, &["TCPFastRetrans"]),
+			slow_start_retrans: count(table, &["TCPSlowStartRetrans"]),
+			timeouts: count(table, &["TCPTimeouts"]),
+			loss_probes: count(table, &["TCPLossProbes"]),
+			spurious_rtos: count(table, &["TCPSpuriousRTOs"]),
+			syn_retrans: count(table, &["TCPSynRetrans"]),
+			retrans_fail: count(table, &["TCPRetransFail"]),
+			orig_data_sent: count(table, &["TCPOrigDataSent"]),
+			abort_on_data: count(table, &["TCPAbortOnData"]),
+			abort_on_close: count(table, &["TCPAbortOnClose"]),
+			abort_on_memory: count(table, &["TCPAbortOnMemory"]),
+			abort_on_timeout: count(table, &["TCPAbortOnTimeout"]),
+			abort_on_linger: count(table, &["TCPAbortOnLinger"]),
+			abort_failed: count(table, &["TCPAbortFailed"]),
+			memory_pressures: count(table, &["TCPMemoryPressures"]),
+			backlog_drop: count(table, &["TCPBacklogDrop"]),
+			req_q_full_do_cookies: count(table, &["TCPReqQFullDoCookies"]),
+			req_q_full_drop: count(table, &["TCPReqQFullDrop"]),
+			ofo_drop: count(table, &["TCPOFODrop"]),
+			rcv_q_drop: count(table, &["TCPRcvQDrop"]),
+			zero_window_drop: count(table, &["TCPZeroWindowDrop"]),
+			time_wait_overflow: count(table, &["TCPTimeWaitOverflow"]),
+		}
+	}
+}
+
+fn parse_value(path: &str, line: &str, value: &str) -> Result<i64> {
+	value.parse().map_err(|err| Error::ParseInt {
+		path: path.into(),
+		contents: line.to_string(),
+		source: err,
+	})
+}
+
+/// Parses `/proc/net/snmp` and `/proc/net/netstat`, where each protocol has a line with the
+/// names followed by a line with the values, both starting with the protocol such as `Tcp:`.
+fn parse_tables(path: &str, contents: &str) -> Result<HashMap<String, Table>> {
+	let mut tables = HashMap::new();
+	let mut lines = contents.lines();
+
+	while let Some(names) = lines.next() {
+		let missing_data = || Error::MissingData {
+			path: path.into(),
+			contents: names.to_string(),
+		};
+
+		let line = lines.next().ok_or_else(missing_data)?;
+		let (protocol, names) = names.split_once(':').ok_or_else(missing_data)?;
+		let values = match line.split_once(':') {
+			Some((other, values)) if other == protocol => values,
+			_ => return Err(missing_data()),
+		};
+
+		let names = names.split_whitespace().collect::<Vec<_>>();
+		let values = values.split_whitespace().collect::<Vec<_>>();
+		if names.len() != values.len() {
+			return Err(missing_data());
+		}
+
+		let mut table = Table::new();
+		for (name, value) in names.into_iter().zip(values) {
+			table.insert(name.to_string(), parse_value(path, line, value)?);
+		}
+		tables.insert(protocol.to_string(), table);
+	}
+
+	Ok(tables)
+}
+
+/// Prefixes of the names in `/proc/net/snmp6`, where prefixes of other prefixes come last.
+const SNMP6_PROTOCOLS: [&str; 4] = ["Icmp6", "UdpLite6", "Udp6", "Ip6"];
+
+/// Parses `/proc/net/snmp6`, which has one name and value per line, such as `Ip6InReceives 3`.
+fn parse_snmp6(contents: &str) -> Result<HashMap<String, Table>> {
+	let mut tables: HashMap<String, Table> = HashMap::new();
+
+	for line in contents.lines() {
+		let (name, value) = match line.split_whitespace().collect::<Vec<_>>()[..] {
+			[name, value] => (name, value),
+			_ => {
+				return Err(Error::MissingData {
+					path: PROC_NET_SNMP6.into(),
+					contents: line.to_string(),
+				})
+			}
+		};
+
+		let (protocol, name) = match SNMP6_PROTOCOLS
+			.iter()
+			.find_map(|protocol| Some((*protocol, name.strip_prefix(protocol)?)))
+		{
+			Some(split) => split,
+			None => continue,
+		};
+
+		tables
+			.entry(protocol.to_string())
+			.or_default()
+			.insert(name.to_string(), parse_value(PROC_NET_SNMP6, line, value)?);
+	}
+
+	Ok(tables)
+}
+
+fn table<'a>(tables: &'a HashMap<String, Table>, path: &str, protocol: &str) -> Result<&'a Table> {
+	tables.get(protocol).ok_or_else(|| Error::MissingData {
+		path: path.into(),
+		contents: protocol.to_string(),
+	})
+}
+
+/// New function, not in Python psutil.
+///
+/// Reads the protocol counters of the network namespace of the current process from
+/// `/proc/net/snmp`, `/proc/net/snmp6` and `/proc/net/netstat`.
+pub fn net_protocol_stats() -> Result<NetProtocolStats> {
+	let snmp = parse_tables(PROC_NET_SNMP, &read_file(PROC_NET_SNMP)?)?;
+	let netstat = parse_tables(PROC_NET_NETSTAT, &read_file(PROC_NET_NETSTAT)?)?;
+	let snmp6 = match read_file(PROC_NET_SNMP6) {
+		Ok(contents) => Some(parse_snmp6(&contents)?),
+		// IPv6 is disabled
+		Err(Error::ReadFile { source, .. }) if source.kind() == io::ErrorKind::NotFound => None,
+		Err(e) => return Err(e),
+	};
+	let table6 = |protocol| snmp6.as_ref()?.get(protocol);
+
+	Ok(NetProtocolStats {
+		ip: IpStats::from_table(table(&snmp, PROC_NET_SNMP, "Ip")?),
+		ip6: table6("Ip6").map(IpStats::from_table),
+		icmp: IcmpStats::from_table(table(&snmp, PROC_NET_SNMP, "Icmp")?),
+		icmp6: table6("Icmp6").map(IcmpStats::from_table),
+		tcp: TcpStats::from_table(table(&snmp, PROC_NET_SNMP, "Tcp")?),
+		tcp_ext: TcpExtStats::from_table(table(&netstat, PROC_NET_NETSTAT, "TcpExt")?),
+		udp: UdpStats::from_table(table(&snmp, PROC_NET_SNMP, "Udp")?),
+		udp6: table6("Udp6").map(UdpStats::from_table),
+	})
+}
+
+#[cfg(test)]
+mod unit_tests {
+	use super::*;
+
+	#[test]
+	fn test_parse_tables() {
+		let contents = "Tcp: RtoAlgorithm MaxConn ActiveOpens CurrEstab RetransSegs\n\
+			Tcp: 1 -1 22 2 7\n\
+			Udp: InDatagrams NoPorts\n\
+			Udp: 18 3\n";
+		let tables = parse_tables(PROC_NET_SNMP, contents).unwrap();
+
+		assert_eq!(tables["Tcp"]["MaxConn"], -1);
+		let tcp = TcpStats::from_table(&tables["Tcp"]);
+		assert_eq!(tcp.active_opens(), 22);
+		assert_eq!(tcp.retrans_segs(), 7);
+		// missing in older kernels
+		assert_eq!(tcp.in_csum_errors(), 0);
+		assert_eq!(UdpStats::from_table(&tables["Udp"]).no_ports(), 3);
+
+		assert!(parse_tables(PROC_NET_SNMP, "Tcp: ActiveOpens CurrEstab\nTcp: 1\n").is_err());
+		assert!(parse_tables(PROC_NET_SNMP, "Tcp: ActiveOpens\nUdp: 1\n").is_err());
+	}
+
+	#[test]
+	fn test_parse_snmp6() {
+		let contents = "Ip6InReceives                   \t3\n\
+			Ip6OutForwDatagrams             \t1\n\
+			Icmp6InEchoReplies              \t2\n\
+			Udp6NoPorts                     \t4\n\
+			UdpLite6NoPorts                 \t5\n";
+		let tables = parse_snmp6(contents).unwrap();
+
+		let ip6 = IpStats::from_table(&tables["Ip6"]);
+		assert_eq!(ip6.in_receives(), 3);
+		assert_eq!(ip6.forw_datagrams(), 1);
+		assert_eq!(IcmpStats::from_table(&tables["Icmp6"]).in_echo_reps(), 2);
+		assert_eq!(UdpStats::from_table(&tables["Udp6"]).no_ports(), 4);
+		assert_eq!(tables["UdpLite6"]["NoPorts"], 5);
+	}
+
+	#[test]
+	fn test_net_protocol_stats() {
+		let stats = net_protocol_stats().unwrap();
+
+		assert!(stats.ip().in_receives() >= stats.ip().in_delivers());
+		assert!(stats.tcp_ext().listen_drops() >= stats.tcp_ext().listen_overflows());
+	}
+}
@@ -3,8 +3,33 @@
 
 use std::collections::BTreeMap;
 
-use crate::process::{self, Process};
+#[cfg(target_os = "linux")]
+use crate::process::os::linux::ProcfsFields;
+use crate::process::{self, Process, ProcessError, ProcessResult, Thread};
 use crate::{Pid, Result};
+
+/// New struct, not in Python psutil.
+///
+/// The changes between two updates of a `ProcessCollector`, sorted by PID.
+#[derive(Debug, Clone, Default)]
+pub struct ProcessDiff {
+	/// Processes that were added to the collector.
+	pub spawned: Vec<Pid>,
+
+	/// Processes that were removed from the collector, with their last known data.
+	pub exited: Vec<Process>,
+
+	/// Processes that were replaced by a new process with the same PID, with their last known
+	/// data.
+	pub reused: Vec<Process>,
+}
+
+impl ProcessDiff {
+	pub fn is_empty(&self) -> bool {
+		self.spawned.is_empty() && self.exited.is_empty() && self.reused.is_empty()
+	}
+}
+
 // FIXME: Process cannot be serialized/deserialize, as a result,
 //        neither this can be.
 // #[cfg_attr(feature = "serde", serde(crate = "renamed_serde"))]
@@ -12,6 +37,11 @@ use crate::{Pid, Result};
 #[derive(Debug, Clone)]
 pub struct ProcessCollector {
 	pub processes: BTreeMap<Pid, Process>,
+
+	/// Files read for each process on every update. `/proc/[pid]/stat` is always read and
+	/// `/proc/[pid]/cmdline` is never read.
+	#[cfg(target_os = "linux")]
+	pub fields: ProcfsFields,
 }
 
 /// Used to maintain a list of up-to-date processes while persisting cached data within the process
@@ -19,38 +49,130 @@ pub struct ProcessCollector {
 /// CPU percent.
 impl ProcessCollector {
 	pub fn new() -> Result<ProcessCollector> {
-		let processes = process::processes()?
+		let processes = ProcessCollector::collect()?;
+
+		Ok(ProcessCollector {
+			processes,
+			#[cfg(target_os = "linux")]
+			fields: ProcfsFields::NONE,
+		})
+	}
+
+	/// New function, not in Python psutil.
+	///
+	/// Also reads the given files for each process, see `Oneshot` for accessing the data.
+	#[cfg(target_os = "linux")]
+	pub fn with_fields(fields: ProcfsFields) -> Result<ProcessCollector> {
+		let mut collector = ProcessCollector {
+			processes: BTreeMap::new(),
+			fields,
+		};
+		collector.update()?;
+
+		Ok(collector)
+	}
+
+	fn collect() -> Result<BTreeMap<Pid, Process>> {
+		Ok(process::processes()?
 			.into_iter()
 			.filter_map(|process| process.ok())
 			.map(|process| (process.pid(), process))
-			.collect();
-
-		Ok(ProcessCollector { processes })
+			.collect())
 	}
 
-	pub fn update(&mut self) -> Result<()> {
-		let new = ProcessCollector::new()?.processes;
+	/// Returns the processes that were spawned, exited or had their PID reused since the last
+	/// update.
+	pub fn update(&mut self) -> Result<ProcessDiff> {
+		let new = ProcessCollector::collect()?;
+		let mut diff = ProcessDiff::default();
 
 		// remove processes with a PID that is no longer in use
 		let to_remove: Vec<Pid> = self
 			.processes
-			.iter()
-			.filter(|(pid, _process)| !new.contains_key(pid))
-			.map(|(pid, _process)| *pid)
+			.keys()
+			.filter(|pid| !new.contains_key(pid))
+			.copied()
+			.collect();
+		for pid in to_remove {
+			if let Some(process) = self.processes.remove(&pid) {
+				diff.exited.push(process);
+			}
+		}
+
+		for (pid, process) in new {
+			match self.processes.get_mut(&pid) {
+				// keep the cached data used for CPU percent and update data used for oneshot
+				Some(old) if *old == process => {
+					#[cfg(target_os = "linux")]
+					{
+						old.procfs_stat = process.procfs_stat;
+					}
+				}
+				// add new processes and replace processes with reused PIDs
+				_ => match self.processes.insert(pid, process) {
+					Some(old) => diff.reused.push(old),
+					None => diff.spawned.push(pid),
+				},
+			}
+		}
+
+		#[cfg(target_os = "linux")]
+		{
+			for process in self.processes.values_mut() {
+				process.refresh_procfs(self.fields);
+			}
+		}
+
+		Ok(diff)
+	}
+}
+
+/// New struct, not in Python psutil.
+///
+/// Used to maintain a list of up-to-date threads of a process, in the same way
+/// `ProcessCollector` does for processes, so that thread CPU percent can be calculated between
+/// updates.
+#[derive(Debug, Clone)]
+pub struct ThreadCollector {
+	pub process: Process,
+	pub threads: BTreeMap<Pid, Thread>,
+}
+
+impl ThreadCollector {
+	pub fn new(process: Process) -> ProcessResult<ThreadCollector> {
+		let threads = process
+			.threads()?
+			.into_iter()
+			.map(|thread| (thread.id(), thread))
 			.collect();
-		for id in to_remove {
-			self.processes.remove(&id);
+
+		Ok(ThreadCollector { process, threads })
+	}
+
+	/// Fails with `NoSuchProcess` once the process has exited or its PID has been reused.
+	pub fn update(&mut self) -> ProcessResult<()> {
+		if !self.process.is_running() {
+			return Err(ProcessError::NoSuchProcess {
+				pid: self.process.pid(),
+			});
 		}
 
-		new.into_iter().for_each(|(pid, process)| {
-			// add new processes and replace processes with reused PIDs
-			if !self.processes.contains_key(&pid) || self.processes[&pid] != process {
-				self.processes.insert(pid, process);
-			} else {
-				// Update data used for oneshot.
-				#[cfg(target_os = "linux")]
-				{
-					self.processes.get_mut(&pid).unwrap().procfs_stat = process.procfs_stat;
+		let new = ThreadCollector::new(self.process.clone())?.threads;
+
+		// remove threads that have exited
+		self.threads.retain(|id, _thread| new.contains_key(id));
+
+		new.into_iter().for_each(|(id, thread)| {
+			// add new threads and replace threads with reused IDs
+			match self.threads.get_mut(&id) {
+				Some(old) if *old == thread => {
+					old.name = thread.name;
+					old.status = thread.status;
+					old.cpu_times = thread.cpu_times;
+					old.cpu_num = thread.cpu_num;
+				}
+				_ => {
+					self.threads.insert(id, thread);
 				}
 			}
 		});
@@ -58,3 +180,69 @@ impl ProcessCollector {
 		Ok(())
 	}
 }
+
+#[cfg(test)]
+mod unit_tests {
+	use super::*;
+
+	#[test]
+	fn test_process_collector() {
+		let mut collector = ProcessCollector::new().unwrap();
+		let mut child = std::process::Command::new("sleep")
+			.arg("10")
+			.spawn()
+			.unwrap();
+
+		let diff = collector.update().unwrap();
+		assert!(diff.spawned.contains(&child.id()));
+		assert!(collector.processes.contains_key(&child.id()));
+
+		child.kill().unwrap();
+		child.wait().unwrap();
+
+		let diff = collector.update().unwrap();
+		assert!(diff
+			.exited
+			.iter()
+			.any(|process| process.pid() == child.id()));
+		assert!(!collector.processes.contains_key(&child.id()));
+	}
+
+	#[cfg(target_os = "linux")]
+	#[test]
+	fn test_process_collector_fields() {
+		use crate::process::os::linux::Oneshot;
+
+		let collector = ProcessCollector::with_fields(ProcfsFields::STATM).unwrap();
+		let current = &collector.processes[&std::process::id()];
+
+		assert!(current.memory_info_oneshot().is_some());
+		assert!(current.procfs_status_oneshot().is_none());
+		assert!(current.io_counters_oneshot().is_none());
+	}
+
+	#[test]
+	fn test_thread_collector() {
+		let handle =
+			std::thread::spawn(|| std::thread::sleep(std::time::Duration::from_millis(500)));
+
+		let mut collector = ThreadCollector::new(Process::current().unwrap()).unwrap();
+		assert!(collector.threads.len() >= 2);
+		assert!(collector.threads.contains_key(&collector.process.pid()));
+
+		collector.update().unwrap();
+		for thread in collector.threads.values() {
+			assert_eq!(thread.pid(), collector.process.pid());
+		}
+		// other test threads may exit at any time
+		let tid = nix::unistd::gettid().as_raw() as Pid;
+		assert!(collector
+			.threads
+			.get_mut(&tid)
+			.unwrap()
+			.cpu_percent()
+			.is_ok());
+
+		handle.join().unwrap();
+	}
+}
@@ -1,5 +1,7 @@
 use std::io;
 
+use nix::errno::Errno;
+
 use crate::{Error, Pid};
 
 pub type ProcessResult<T> = std::result::Result<T, ProcessError>;
@@ -15,6 +17,9 @@ pub enum ProcessError {
 	#[error("Access denied for process {}", pid)]
 	AccessDenied { pid: Pid },
 
+	#[error("Timeout expired while waiting for process {}", pid)]
+	TimeoutExpired { pid: Pid },
+
 	#[error("psutil error for process {}: {}", pid, source)]
 	PsutilError { pid: Pid, source: Error },
 }
@@ -24,6 +29,11 @@ pub(crate) fn psutil_error_to_process_error(e: Error, pid: Pid) -> ProcessError
 		Error::ReadFile { source, .. } | Error::OsError { source, .. } => {
 			io_error_to_process_error(source, pid)
 		}
+		Error::NixError { source } => match source {
+			Errno::ESRCH => ProcessError::NoSuchProcess { pid },
+			Errno::EPERM | Errno::EACCES => ProcessError::AccessDenied { pid },
+			_ => ProcessError::PsutilError { pid, source: e },
+		},
 		_ => ProcessError::PsutilError { pid, source: e },
 	}
 }
@@ -32,6 +42,7 @@ pub(crate) fn io_error_to_process_error(e: io::Error, pid: Pid) -> ProcessError
 	match e.kind() {
 		io::ErrorKind::NotFound => ProcessError::NoSuchProcess { pid },
 		io::ErrorKind::PermissionDenied => ProcessError::AccessDenied { pid },
+		_ if e.raw_os_error() == Some(Errno::ESRCH as i32) => ProcessError::NoSuchProcess { pid },
 		_ => ProcessError::PsutilError {
 			pid,
 			source: Error::OsError { source: e },
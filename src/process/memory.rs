@@ -5,14 +5,27 @@ use serde::{Deserialize, Serialize};
 use crate::Bytes;
 
 #[cfg(target_os = "linux")]
-use crate::process::os::linux::ProcfsStatm;
+use crate::process::os::linux::{ProcfsSmaps, ProcfsStatm};
 #[cfg(target_os = "macos")]
 use crate::Count;
 
 #[cfg_attr(feature = "serde", serde(crate = "renamed_serde"))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum MemType {
-	// TODO
+	Rss,
+	Vms,
+	Uss,
+	/// Linux only
+	Pss,
+	/// Linux only
+	Swap,
+	/// Linux only
+	Shared,
+	/// Linux only
+	Text,
+	/// Linux only
+	Data,
 }
 
 #[allow(dead_code)]
@@ -49,6 +62,53 @@ impl MemoryInfo {
 	pub fn shared(&self) -> Bytes {
 		self.shared
 	}
+
+	#[cfg(target_os = "linux")]
+	pub fn text(&self) -> Bytes {
+		self.text
+	}
+
+	#[cfg(target_os = "linux")]
+	pub fn data(&self) -> Bytes {
+		self.data
+	}
+}
+
+#[cfg_attr(feature = "serde", serde(crate = "renamed_serde"))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct MemoryFullInfo {
+	pub(crate) memory_info: MemoryInfo,
+	pub(crate) uss: Bytes,
+
+	#[cfg(target_os = "linux")]
+	pub(crate) pss: Bytes,
+	#[cfg(target_os = "linux")]
+	pub(crate) swap: Bytes,
+}
+
+impl MemoryFullInfo {
+	/// The same values as `Process::memory_info`.
+	pub fn memory_info(&self) -> &MemoryInfo {
+		&self.memory_info
+	}
+
+	/// Unique Set Size: the memory that would be freed if the process exited.
+	pub fn uss(&self) -> Bytes {
+		self.uss
+	}
+
+	/// Proportional Set Size: shared memory is divided evenly between the processes sharing it.
+	#[cfg(target_os = "linux")]
+	pub fn pss(&self) -> Bytes {
+		self.pss
+	}
+
+	/// Memory that has been swapped out.
+	#[cfg(target_os = "linux")]
+	pub fn swap(&self) -> Bytes {
+		self.swap
+	}
 }
 
 #[cfg(target_os = "linux")]
@@ -64,6 +124,18 @@ impl From<ProcfsStatm> for MemoryInfo {
 	}
 }
 
+#[cfg(target_os = "linux")]
+impl From<(ProcfsStatm, ProcfsSmaps)> for MemoryFullInfo {
+	fn from((statm, smaps): (ProcfsStatm, ProcfsSmaps)) -> Self {
+		MemoryFullInfo {
+			memory_info: statm.into(),
+			uss: smaps.uss(),
+			pss: smaps.pss,
+			swap: smaps.swap,
+		}
+	}
+}
+
 #[cfg(target_os = "macos")]
 impl From<darwin_libproc::proc_taskinfo> for MemoryInfo {
 	fn from(info: darwin_libproc::proc_taskinfo) -> Self {
@@ -8,6 +8,9 @@ pub mod os;
 mod process;
 mod status;
 mod sys;
+mod thread;
+mod tree;
+mod wait;
 
 pub use nix::sys::signal::Signal;
 
@@ -19,3 +22,6 @@ pub use open_file::*;
 pub use process::*;
 pub use status::*;
 pub use sys::*;
+pub use thread::*;
+pub use tree::*;
+pub use wait::*;
@@ -0,0 +1,262 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use std::fmt;
+use std::iter::FromIterator;
+
+use crate::process::os::linux::ProcfsStatus;
+
+/// A Linux capability, see `capabilities(7)`.
+#[cfg_attr(feature = "serde", serde(crate = "renamed_serde"))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Capability {
+	Chown,
+	DacOverride,
+	DacReadSearch,
+	Fowner,
+	Fsetid,
+	Kill,
+	Setgid,
+	Setuid,
+	Setpcap,
+	LinuxImmutable,
+	NetBindService,
+	NetBroadcast,
+	NetAdmin,
+	NetRaw,
+	IpcLock,
+	IpcOwner,
+	SysModule,
+	SysRawio,
+	SysChroot,
+	SysPtrace,
+	SysPacct,
+	SysAdmin,
+	SysBoot,
+	SysNice,
+	SysResource,
+	SysTime,
+	SysTtyConfig,
+	Mknod,
+	Lease,
+	AuditWrite,
+	AuditControl,
+	Setfcap,
+	MacOverride,
+	MacAdmin,
+	Syslog,
+	WakeAlarm,
+	BlockSuspend,
+	AuditRead,
+	Perfmon,
+	Bpf,
+	CheckpointRestore,
+}
+
+impl Capability {
+	/// Ordered by number.
+	pub const ALL: [Capability; 41] = [
+		Capability::Chown,
+		Capability::DacOverride,
+		Capability::DacReadSearch,
+		Capability::Fowner,
+		Capability::Fsetid,
+		Capability::Kill,
+		Capability::Setgid,
+		Capability::Setuid,
+		Capability::Setpcap,
+		Capability::LinuxImmutable,
+		Capability::NetBindService,
+		Capability::NetBroadcast,
+		Capability::NetAdmin,
+		Capability::NetRaw,
+		Capability::IpcLock,
+		Capability::IpcOwner,
+		Capability::SysModule,
+		Capability::SysRawio,
+		Capability::SysChroot,
+		Capability::SysPtrace,
+		Capability::SysPacct,
+		Capability::SysAdmin,
+		Capability::SysBoot,
+		Capability::SysNice,
+		Capability::SysResource,
+		Capability::SysTime,
+		Capability::SysTtyConfig,
+		Capability::Mknod,
+		Capability::Lease,
+		Capability::AuditWrite,
+		Capability::AuditControl,
+		Capability::Setfcap,
+		Capability::MacOverride,
+		Capability::MacAdmin,
+		Capability::Syslog,
+		Capability::WakeAlarm,
+		Capability::BlockSuspend,
+		Capability::AuditRead,
+		Capability::Perfmon,
+		Capability::Bpf,
+		Capability::CheckpointRestore,
+	];
+
+	/// The number of the capability, such as 21 for `CAP_SYS_ADMIN`.
+	pub fn number(self) -> u32 {
+		self as u32
+	}
+
+	pub fn from_number(number: u32) -> Option<Capability> {
+		Capability::ALL.get(number as usize).copied()
+	}
+
+	/// The name of the capability, such as `CAP_SYS_ADMIN`.
+	pub fn name(self) -> &'static str {
+		match self {
+			Capability::Chown => "CAP_CHOWN",
+			Capability::DacOverride => "CAP_DAC_OVERRIDE",
+			Capability::DacReadSearch => "CAP_DAC_READ_SEARCH",
+			Capability::Fowner => "CAP_FOWNER",
+			Capability::Fsetid => "CAP_FSETID",
+			Capability::Kill => "CAP_KILL",
+			Capability::Setgid => "CAP_SETGID",
+			Capability::Setuid => "CAP_SETUID",
+			Capability::Setpcap => "CAP_SETPCAP",
+			Capability::LinuxImmutable => "CAP_LINUX_IMMUTABLE",
+			Capability::NetBindService => "CAP_NET_BIND_SERVICE",
+			Capability::NetBroadcast => "CAP_NET_BROADCAST",
+			Capability::NetAdmin => "CAP_NET_ADMIN",
+			Capability::NetRaw => "CAP_NET_RAW",
+			Capability::IpcLock => "CAP_IPC_LOCK",
+			Capability::IpcOwner => "CAP_IPC_OWNER",
+			Capability::SysModule => "CAP_SYS_MODULE",
+			Capability::SysRawio => "CAP_SYS_RAWIO",
+			Capability::SysChroot => "CAP_SYS_CHROOT",
+			Capability::SysPtrace => "CAP_SYS_PTRACE",
+			Capability::SysPacct => "CAP_SYS_PACCT",
+			Capability::SysAdmin => "CAP_SYS_ADMIN",
+			Capability::SysBoot => "CAP_SYS_BOOT",
+			Capability::SysNice => "CAP_SYS_NICE",
+			Capability::SysResource => "CAP_SYS_RESOURCE",
+			Capability::SysTime => "CAP_SYS_TIME",
+			Capability::SysTtyConfig => "CAP_SYS_TTY_CONFIG",
+			Capability::Mknod => "CAP_MKNOD",
+			Capability::Lease => "CAP_LEASE",
+			Capability::AuditWrite => "CAP_AUDIT_WRITE",
+			Capability::AuditControl => "CAP_AUDIT_CONTROL",
+			Capability::Setfcap => "CAP_SETFCAP",
+			Capability::MacOverride => "CAP_MAC_OVERRIDE",
+			Capability::MacAdmin => "CAP_MAC_ADMIN",
+			Capability::Syslog => "CAP_SYSLOG",
+			Capability::WakeAlarm => "CAP_WAKE_ALARM",
+			Capability::BlockSuspend => "CAP_BLOCK_SUSPEND",
+			Capability::AuditRead => "CAP_AUDIT_READ",
+			Capability::Perfmon => "CAP_PERFMON",
+			Capability::Bpf => "CAP_BPF",
+			Capability::CheckpointRestore => "CAP_CHECKPOINT_RESTORE",
+		}
+	}
+}
+
+impl fmt::Display for Capability {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str(self.name())
+	}
+}
+
+/// A set of capabilities, decoded from a mask in `/proc/[pid]/status`.
+///
+/// Capabilities that are newer than this crate are kept in the mask but not yielded by `iter`.
+#[cfg_attr(feature = "serde", serde(crate = "renamed_serde"))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct CapabilitySet {
+	mask: u64,
+}
+
+impl CapabilitySet {
+	pub fn from_mask(mask: u64) -> CapabilitySet {
+		CapabilitySet { mask }
+	}
+
+	pub fn mask(&self) -> u64 {
+		self.mask
+	}
+
+	pub fn contains(&self, capability: Capability) -> bool {
+		self.mask & (1 << capability.number()) != 0
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.mask == 0
+	}
+
+	/// Iterates over the known capabilities in the set, ordered by number.
+	pub fn iter(&self) -> impl Iterator<Item = Capability> + '_ {
+		Capability::ALL
+			.iter()
+			.copied()
+			.filter(move |capability| self.contains(*capability))
+	}
+}
+
+impl FromIterator<Capability> for CapabilitySet {
+	fn from_iter<I: IntoIterator<Item = Capability>>(iter: I) -> Self {
+		CapabilitySet {
+			mask: iter
+				.into_iter()
+				.fold(0, |mask, capability| mask | 1 << capability.number()),
+		}
+	}
+}
+
+/// The capability sets of a process. Sets that are missing from `/proc/[pid]/status` are empty.
+#[cfg_attr(feature = "serde", serde(crate = "renamed_serde"))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Capabilities {
+	pub inheritable: CapabilitySet,
+	pub permitted: CapabilitySet,
+	pub effective: CapabilitySet,
+	pub bounding: CapabilitySet,
+	/// Empty before Linux 4.3.
+	pub ambient: CapabilitySet,
+}
+
+impl From<&ProcfsStatus> for Capabilities {
+	fn from(procfs_status: &ProcfsStatus) -> Self {
+		Capabilities {
+			inheritable: CapabilitySet::from_mask(procfs_status.cap_inh.unwrap_or_default()),
+			permitted: CapabilitySet::from_mask(procfs_status.cap_prm.unwrap_or_default()),
+			effective: CapabilitySet::from_mask(procfs_status.cap_eff.unwrap_or_default()),
+			bounding: CapabilitySet::from_mask(procfs_status.cap_bnd.unwrap_or_default()),
+			ambient: CapabilitySet::from_mask(procfs_status.cap_amb.unwrap_or_default()),
+		}
+	}
+}
+
+#[cfg(test)]
+mod unit_tests {
+	use super::*;
+
+	#[test]
+	fn test_capability_set() {
+		let set = CapabilitySet::from_mask(0x20_0000 | 1 << 63);
+
+		assert!(set.contains(Capability::SysAdmin));
+		assert!(!set.contains(Capability::Chown));
+		assert_eq!(set.iter().collect::<Vec<_>>(), vec![Capability::SysAdmin]);
+		assert_eq!(
+			vec![Capability::SysAdmin, Capability::Chown]
+				.into_iter()
+				.collect::<CapabilitySet>()
+				.mask(),
+			0x20_0001
+		);
+
+		for (number, capability) in Capability::ALL.iter().enumerate() {
+			assert_eq!(capability.number(), number as u32);
+			assert_eq!(Capability::from_number(number as u32), Some(*capability));
+		}
+		assert_eq!(Capability::SysAdmin.to_string(), "CAP_SYS_ADMIN");
+	}
+}
@@ -0,0 +1,123 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use std::collections::BTreeSet;
+use std::iter::FromIterator;
+
+use nix::sched::{self, CpuSet as NixCpuSet};
+use nix::unistd;
+
+use crate::process::{psutil_error_to_process_error, ProcessResult};
+use crate::Pid;
+
+/// A set of logical CPUs, used for CPU affinity.
+///
+/// New struct, Python psutil uses a list of CPU numbers.
+#[cfg_attr(feature = "serde", serde(crate = "renamed_serde"))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct CpuSet {
+	cpus: BTreeSet<u32>,
+}
+
+impl CpuSet {
+	/// Creates an empty set.
+	pub fn new() -> CpuSet {
+		CpuSet::default()
+	}
+
+	/// Returns `true` if the CPU was not already in the set.
+	pub fn insert(&mut self, cpu: u32) -> bool {
+		self.cpus.insert(cpu)
+	}
+
+	/// Returns `true` if the CPU was in the set.
+	pub fn remove(&mut self, cpu: u32) -> bool {
+		self.cpus.remove(&cpu)
+	}
+
+	pub fn contains(&self, cpu: u32) -> bool {
+		self.cpus.contains(&cpu)
+	}
+
+	pub fn len(&self) -> usize {
+		self.cpus.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.cpus.is_empty()
+	}
+
+	/// Iterates over the CPUs in ascending order.
+	pub fn iter(&self) -> impl Iterator<Item = u32> + '_ {
+		self.cpus.iter().copied()
+	}
+}
+
+impl FromIterator<u32> for CpuSet {
+	fn from_iter<I: IntoIterator<Item = u32>>(iter: I) -> Self {
+		CpuSet {
+			cpus: iter.into_iter().collect(),
+		}
+	}
+}
+
+impl IntoIterator for CpuSet {
+	type Item = u32;
+	type IntoIter = std::collections::btree_set::IntoIter<u32>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.cpus.into_iter()
+	}
+}
+
+/// Gets the CPU affinity of a thread, which is the main thread if `tid` is a PID.
+pub(crate) fn sched_getaffinity(tid: Pid) -> ProcessResult<CpuSet> {
+	let nix_cpu_set = sched::sched_getaffinity(unistd::Pid::from_raw(tid as i32))
+		.map_err(|e| psutil_error_to_process_error(e.into(), tid))?;
+
+	Ok((0..NixCpuSet::count())
+		.filter(|cpu| nix_cpu_set.is_set(*cpu).unwrap_or(false))
+		.map(|cpu| cpu as u32)
+		.collect())
+}
+
+/// Sets the CPU affinity of a thread. Only affects the main thread if `tid` is a PID.
+pub(crate) fn sched_setaffinity(tid: Pid, cpus: &CpuSet) -> ProcessResult<()> {
+	let mut nix_cpu_set = NixCpuSet::new();
+	for cpu in cpus.iter() {
+		nix_cpu_set
+			.set(cpu as usize)
+			.map_err(|e| psutil_error_to_process_error(e.into(), tid))?;
+	}
+
+	sched::sched_setaffinity(unistd::Pid::from_raw(tid as i32), &nix_cpu_set)
+		.map_err(|e| psutil_error_to_process_error(e.into(), tid))
+}
+
+#[cfg(test)]
+mod unit_tests {
+	use super::*;
+
+	#[test]
+	fn test_cpu_set() {
+		let mut cpu_set: CpuSet = vec![3, 1].into_iter().collect();
+
+		assert!(cpu_set.insert(2));
+		assert!(!cpu_set.insert(2));
+		assert!(cpu_set.remove(3));
+		assert!(cpu_set.contains(1));
+		assert!(!cpu_set.contains(3));
+		assert_eq!(cpu_set.iter().collect::<Vec<_>>(), vec![1, 2]);
+	}
+
+	#[test]
+	fn test_sched_affinity() {
+		let tid = std::process::id();
+		let cpu_set = sched_getaffinity(tid).unwrap();
+
+		assert!(!cpu_set.is_empty());
+		sched_setaffinity(tid, &cpu_set).unwrap();
+		assert_eq!(sched_getaffinity(tid).unwrap(), cpu_set);
+	}
+}
@@ -0,0 +1,64 @@
+use std::ops::{BitOr, BitOrAssign};
+
+/// New struct, not in Python psutil.
+///
+/// A set of files in `/proc/[pid]/` to read in one pass. Combine them with `|`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ProcfsFields(u8);
+
+impl ProcfsFields {
+	pub const NONE: ProcfsFields = ProcfsFields(0);
+	/// `/proc/[pid]/stat`, used for the name, status, CPU times and number of threads.
+	/// Always read by `ProcessCollector`.
+	pub const STAT: ProcfsFields = ProcfsFields(1);
+	/// `/proc/[pid]/statm`, used for memory info.
+	pub const STATM: ProcfsFields = ProcfsFields(1 << 1);
+	/// `/proc/[pid]/status`, used for UIDs, GIDs and context switches.
+	pub const STATUS: ProcfsFields = ProcfsFields(1 << 2);
+	/// `/proc/[pid]/io`, usually only readable for processes of the same user.
+	pub const IO: ProcfsFields = ProcfsFields(1 << 3);
+	/// `/proc/[pid]/cmdline`. Not cached by `ProcessCollector`.
+	pub const CMDLINE: ProcfsFields = ProcfsFields(1 << 4);
+	pub const ALL: ProcfsFields = ProcfsFields((1 << 5) - 1);
+
+	/// Returns `true` if all fields of `other` are in the set.
+	pub fn contains(self, other: ProcfsFields) -> bool {
+		self.0 & other.0 == other.0
+	}
+
+	pub fn is_empty(self) -> bool {
+		self.0 == 0
+	}
+}
+
+impl BitOr for ProcfsFields {
+	type Output = ProcfsFields;
+
+	fn bitor(self, other: ProcfsFields) -> ProcfsFields {
+		ProcfsFields(self.0 | other.0)
+	}
+}
+
+impl BitOrAssign for ProcfsFields {
+	fn bitor_assign(&mut self, other: ProcfsFields) {
+		self.0 |= other.0;
+	}
+}
+
+#[cfg(test)]
+mod unit_tests {
+	use super::*;
+
+	#[test]
+	fn test_procfs_fields() {
+		let mut fields = ProcfsFields::STATM | ProcfsFields::IO;
+
+		assert!(fields.contains(ProcfsFields::STATM));
+		assert!(!fields.contains(ProcfsFields::STATUS));
+		assert!(ProcfsFields::ALL.contains(fields));
+		assert!(ProcfsFields::NONE.is_empty());
+
+		fields |= ProcfsFields::STATUS | ProcfsFields::STAT | ProcfsFields::CMDLINE;
+		assert_eq!(fields, ProcfsFields::ALL);
+	}
+}
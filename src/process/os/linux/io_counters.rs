@@ -0,0 +1,281 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use crate::process::{self, procfs_path, psutil_error_to_process_error, ProcessResult};
+use crate::{read_file, Bytes, Count, Error, Pid, Result};
+
+const IO: &str = "io";
+
+/// I/O statistics of a process read from `/proc/[pid]/io`.
+#[cfg_attr(feature = "serde", serde(crate = "renamed_serde"))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct IoCounters {
+	pub(crate) read_count: Count,
+	pub(crate) write_count: Count,
+	pub(crate) read_bytes: Bytes,
+	pub(crate) write_bytes: Bytes,
+	pub(crate) read_chars: Bytes,
+	pub(crate) write_chars: Bytes,
+	pub(crate) cancelled_write_bytes: Bytes,
+}
+
+impl IoCounters {
+	/// Number of read system calls (`syscr`).
+	pub fn read_count(&self) -> Count {
+		self.read_count
+	}
+
+	/// Number of write system calls (`syscw`).
+	pub fn write_count(&self) -> Count {
+		self.write_count
+	}
+
+	/// Number of bytes fetched from the storage layer.
+	pub fn read_bytes(&self) -> Bytes {
+		self.read_bytes
+	}
+
+	/// Number of bytes sent to the storage layer.
+	pub fn write_bytes(&self) -> Bytes {
+		self.write_bytes
+	}
+
+	/// Number of bytes passed to read system calls, including reads served from the page cache
+	/// (`rchar`).
+	pub fn read_chars(&self) -> Bytes {
+		self.read_chars
+	}
+
+	/// Number of bytes passed to write system calls (`wchar`).
+	pub fn write_chars(&self) -> Bytes {
+		self.write_chars
+	}
+
+	/// New method, not in Python psutil.
+	/// Number of written bytes that never reached the storage layer, e.g. because the file was
+	/// truncated before writeback.
+	pub fn cancelled_write_bytes(&self) -> Bytes {
+		self.cancelled_write_bytes
+	}
+}
+
+impl FromStr for IoCounters {
+	type Err = Error;
+
+	fn from_str(contents: &str) -> Result<Self> {
+		let mut io_counters = IoCounters::default();
+
+		for line in contents.lines() {
+			let fields = match line.split_whitespace().collect::<Vec<_>>() {
+				fields if fields.len() >= 2 => Ok(fields),
+				_ => Err(Error::MissingData {
+					path: IO.into(),
+					contents: contents.to_string(),
+				}),
+			}?;
+
+			let field = match fields[0] {
+				"rchar:" => &mut io_counters.read_chars,
+				"wchar:" => &mut io_counters.write_chars,
+				"syscr:" => &mut io_counters.read_count,
+				"syscw:" => &mut io_counters.write_count,
+				"read_bytes:" => &mut io_counters.read_bytes,
+				"write_bytes:" => &mut io_counters.write_bytes,
+				"cancelled_write_bytes:" => &mut io_counters.cancelled_write_bytes,
+				_ => continue,
+			};
+			*field = fields[1].parse().map_err(|err| Error::ParseInt {
+				path: IO.into(),
+				contents: contents.to_string(),
+				source: err,
+			})?;
+		}
+
+		Ok(io_counters)
+	}
+}
+
+/// New function, not in Python psutil.
+pub fn procfs_io(pid: Pid) -> ProcessResult<IoCounters> {
+	let contents =
+		read_file(procfs_path(pid, IO)).map_err(|e| psutil_error_to_process_error(e, pid))?;
+
+	IoCounters::from_str(&contents).map_err(|e| psutil_error_to_process_error(e, pid))
+}
+
+/// Per second I/O rates of a process, as returned by `ProcessIoCollector`.
+///
+/// New struct, not in Python psutil.
+#[cfg_attr(feature = "serde", serde(crate = "renamed_serde"))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct IoRates {
+	pub(crate) read_count: f64,
+	pub(crate) write_count: f64,
+	pub(crate) read_bytes: f64,
+	pub(crate) write_bytes: f64,
+	pub(crate) read_chars: f64,
+	pub(crate) write_chars: f64,
+	pub(crate) cancelled_write_bytes: f64,
+}
+
+impl IoRates {
+	fn new(prev: &IoCounters, current: &IoCounters, elapsed: Duration) -> IoRates {
+		let secs = elapsed.as_secs_f64();
+		let rate = |prev: u64, current: u64| {
+			if secs > 0.0 {
+				current.saturating_sub(prev) as f64 / secs
+			} else {
+				0.0
+			}
+		};
+
+		IoRates {
+			read_count: rate(prev.read_count, current.read_count),
+			write_count: rate(prev.write_count, current.write_count),
+			read_bytes: rate(prev.read_bytes, current.read_bytes),
+			write_bytes: rate(prev.write_bytes, current.write_bytes),
+			read_chars: rate(prev.read_chars, current.read_chars),
+			write_chars: rate(prev.write_chars, current.write_chars),
+			cancelled_write_bytes: rate(prev.cancelled_write_bytes, current.cancelled_write_bytes),
+		}
+	}
+
+	/// Read system calls per second.
+	pub fn read_count(&self) -> f64 {
+		self.read_count
+	}
+
+	/// Write system calls per second.
+	pub fn write_count(&self) -> f64 {
+		self.write_count
+	}
+
+	/// Bytes per second fetched from the storage layer.
+	pub fn read_bytes(&self) -> f64 {
+		self.read_bytes
+	}
+
+	/// Bytes per second sent to the storage layer.
+	pub fn write_bytes(&self) -> f64 {
+		self.write_bytes
+	}
+
+	/// Bytes per second passed to read system calls.
+	pub fn read_chars(&self) -> f64 {
+		self.read_chars
+	}
+
+	/// Bytes per second passed to write system calls.
+	pub fn write_chars(&self) -> f64 {
+		self.write_chars
+	}
+
+	/// Cancelled write bytes per second.
+	pub fn cancelled_write_bytes(&self) -> f64 {
+		self.cancelled_write_bytes
+	}
+}
+
+/// New struct, not in Python psutil.
+///
+/// Used to persist the I/O counters of every process between calls in order to calculate I/O
+/// rates.
+#[derive(Clone, Debug, Default)]
+pub struct ProcessIoCollector {
+	// keyed by PID, the create time is used to detect PID reuse
+	prev: HashMap<Pid, (Duration, IoCounters, Instant)>,
+}
+
+impl ProcessIoCollector {
+	/// Returns the I/O rates of every process since the previous call.
+	///
+	/// Processes that are new since the previous call, or whose I/O counters cannot be read (e.g.
+	/// due to insufficient permissions), are left out, so the first call returns an empty map.
+	pub fn io_rates(&mut self) -> Result<HashMap<Pid, IoRates>> {
+		let mut current = HashMap::new();
+		let mut rates = HashMap::new();
+
+		for process in process::processes()?.into_iter().flatten() {
+			let pid = process.pid();
+			let io_counters = match procfs_io(pid) {
+				Ok(io_counters) => io_counters,
+				Err(_) => continue,
+			};
+			let instant = Instant::now();
+
+			if let Some((create_time, prev, prev_instant)) = self.prev.get(&pid) {
+				if *create_time == process.create_time() {
+					rates.insert(
+						pid,
+						IoRates::new(prev, &io_counters, instant - *prev_instant),
+					);
+				}
+			}
+
+			current.insert(pid, (process.create_time(), io_counters, instant));
+		}
+
+		self.prev = current;
+
+		Ok(rates)
+	}
+}
+
+#[cfg(test)]
+mod unit_tests {
+	use super::*;
+
+	#[test]
+	fn test_parse_io() {
+		let contents = "\
+rchar: 3980
+wchar: 12
+syscr: 9
+syscw: 1
+read_bytes: 4096
+write_bytes: 8192
+cancelled_write_bytes: 0
+";
+		let io_counters = IoCounters::from_str(contents).unwrap();
+
+		assert_eq!(io_counters.read_chars(), 3980);
+		assert_eq!(io_counters.write_chars(), 12);
+		assert_eq!(io_counters.read_count(), 9);
+		assert_eq!(io_counters.write_count(), 1);
+		assert_eq!(io_counters.read_bytes(), 4096);
+		assert_eq!(io_counters.write_bytes(), 8192);
+		assert_eq!(io_counters.cancelled_write_bytes(), 0);
+	}
+
+	#[test]
+	fn test_io_rates() {
+		let prev = IoCounters {
+			read_chars: 1000,
+			..Default::default()
+		};
+		let current = IoCounters {
+			read_chars: 3000,
+			..Default::default()
+		};
+		let rates = IoRates::new(&prev, &current, Duration::from_secs(2));
+
+		assert_eq!(rates.read_chars(), 1000.0);
+		assert_eq!(rates.write_chars(), 0.0);
+	}
+
+	#[test]
+	fn test_process_io_collector() {
+		let mut collector = ProcessIoCollector::default();
+		assert!(collector.io_rates().unwrap().is_empty());
+
+		let pid = std::process::id();
+		let rates = collector.io_rates().unwrap();
+		assert!(rates.contains_key(&pid));
+	}
+}
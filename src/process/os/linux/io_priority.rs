@@ -0,0 +1,133 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use nix::errno::Errno;
+use nix::libc;
+
+use crate::process::{psutil_error_to_process_error, ProcessResult};
+use crate::Pid;
+
+const IOPRIO_WHO_PROCESS: libc::c_int = 1;
+const IOPRIO_CLASS_SHIFT: libc::c_int = 13;
+// since Linux 6.5, the bits between the level and the class hold I/O priority hints
+const IOPRIO_LEVEL_MASK: libc::c_int = 0x7;
+const IOPRIO_LEVEL_MAX: u8 = 7;
+
+/// I/O scheduling class, see `ioprio_set(2)`.
+#[cfg_attr(feature = "serde", serde(crate = "renamed_serde"))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum IoPriorityClass {
+	/// No class was set, the priority is derived from the nice value.
+	None,
+	/// Served first, regardless of other processes.
+	RealTime,
+	/// The default class.
+	BestEffort,
+	/// Only served when no other process needs the disk.
+	Idle,
+}
+
+/// I/O priority of a process, renamed from `ionice` in Python psutil.
+#[cfg_attr(feature = "serde", serde(crate = "renamed_serde"))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct IoPriority {
+	pub class: IoPriorityClass,
+
+	/// From 0 (highest) to 7 (lowest). Only used by the real-time and best-effort classes.
+	pub level: u8,
+}
+
+impl IoPriority {
+	fn from_raw(raw: libc::c_int) -> IoPriority {
+		let class = match raw >> IOPRIO_CLASS_SHIFT {
+			1 => IoPriorityClass::RealTime,
+			2 => IoPriorityClass::BestEffort,
+			3 => IoPriorityClass::Idle,
+			_ => IoPriorityClass::None,
+		};
+
+		IoPriority {
+			class,
+			level: (raw & IOPRIO_LEVEL_MASK) as u8,
+		}
+	}
+
+	fn to_raw(self) -> libc::c_int {
+		let class = match self.class {
+			IoPriorityClass::None => 0,
+			IoPriorityClass::RealTime => 1,
+			IoPriorityClass::BestEffort => 2,
+			IoPriorityClass::Idle => 3,
+		};
+
+		(class << IOPRIO_CLASS_SHIFT) | self.level as libc::c_int
+	}
+}
+
+/// Gets the I/O priority of a thread, which is the main thread if `tid` is a PID.
+pub(crate) fn ioprio_get(tid: Pid) -> ProcessResult<IoPriority> {
+	let raw = unsafe { libc::syscall(libc::SYS_ioprio_get, IOPRIO_WHO_PROCESS, tid) };
+
+	Errno::result(raw)
+		.map(|raw| IoPriority::from_raw(raw as libc::c_int))
+		.map_err(|e| psutil_error_to_process_error(e.into(), tid))
+}
+
+/// Sets the I/O priority of a thread, which is the main thread if `tid` is a PID.
+/// Levels above 7 are rejected with `EINVAL`.
+pub(crate) fn ioprio_set(tid: Pid, priority: IoPriority) -> ProcessResult<()> {
+	// newer kernels would take the upper bits of the level as hints
+	if priority.level > IOPRIO_LEVEL_MAX {
+		return Err(psutil_error_to_process_error(Errno::EINVAL.into(), tid));
+	}
+
+	let result = unsafe {
+		libc::syscall(
+			libc::SYS_ioprio_set,
+			IOPRIO_WHO_PROCESS,
+			tid,
+			priority.to_raw(),
+		)
+	};
+
+	Errno::result(result)
+		.map(|_| ())
+		.map_err(|e| psutil_error_to_process_error(e.into(), tid))
+}
+
+#[cfg(test)]
+mod unit_tests {
+	use super::*;
+	use crate::process::ProcessError;
+
+	#[test]
+	fn test_io_priority_raw() {
+		let priority = IoPriority {
+			class: IoPriorityClass::BestEffort,
+			level: 7,
+		};
+
+		assert_eq!(priority.to_raw(), 0x4007);
+		assert_eq!(IoPriority::from_raw(0x4007), priority);
+		assert_eq!(IoPriority::from_raw(0x6000).class, IoPriorityClass::Idle);
+		assert_eq!(IoPriority::from_raw(0).class, IoPriorityClass::None);
+		// a hint in bits 3-12
+		assert_eq!(IoPriority::from_raw(0x4000 | 1 << 3 | 5).level, 5);
+	}
+
+	#[test]
+	fn test_ioprio_set_invalid_level() {
+		let tid = std::process::id() as Pid;
+		let priority = IoPriority {
+			class: IoPriorityClass::BestEffort,
+			level: 8,
+		};
+
+		assert!(matches!(
+			ioprio_set(tid, priority),
+			Err(ProcessError::PsutilError { .. })
+		));
+	}
+}
@@ -0,0 +1,353 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use std::collections::HashMap;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use crate::process::os::linux::parse_smaps_size;
+use crate::{Bytes, Error, Result};
+
+const SMAPS: &str = "smaps";
+
+/// Path given to anonymous mappings, as in Python psutil.
+const ANONYMOUS: &str = "[anon]";
+
+/// Memory usage of one or more mappings, read from `/proc/[pid]/smaps`.
+///
+/// New struct, not in Python psutil.
+#[cfg_attr(feature = "serde", serde(crate = "renamed_serde"))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MemoryMapUsage {
+	pub(crate) size: Bytes,
+	pub(crate) rss: Bytes,
+	pub(crate) pss: Bytes,
+	pub(crate) shared_clean: Bytes,
+	pub(crate) shared_dirty: Bytes,
+	pub(crate) private_clean: Bytes,
+	pub(crate) private_dirty: Bytes,
+	pub(crate) referenced: Bytes,
+	pub(crate) anonymous: Bytes,
+	pub(crate) swap: Bytes,
+}
+
+impl MemoryMapUsage {
+	/// Size of the address range, mapped or not.
+	pub fn size(&self) -> Bytes {
+		self.size
+	}
+
+	pub fn rss(&self) -> Bytes {
+		self.rss
+	}
+
+	pub fn pss(&self) -> Bytes {
+		self.pss
+	}
+
+	pub fn shared_clean(&self) -> Bytes {
+		self.shared_clean
+	}
+
+	pub fn shared_dirty(&self) -> Bytes {
+		self.shared_dirty
+	}
+
+	/// New method, not in Python psutil.
+	pub fn shared(&self) -> Bytes {
+		self.shared_clean + self.shared_dirty
+	}
+
+	pub fn private_clean(&self) -> Bytes {
+		self.private_clean
+	}
+
+	pub fn private_dirty(&self) -> Bytes {
+		self.private_dirty
+	}
+
+	/// New method, not in Python psutil.
+	pub fn private(&self) -> Bytes {
+		self.private_clean + self.private_dirty
+	}
+
+	pub fn referenced(&self) -> Bytes {
+		self.referenced
+	}
+
+	pub fn anonymous(&self) -> Bytes {
+		self.anonymous
+	}
+
+	pub fn swap(&self) -> Bytes {
+		self.swap
+	}
+
+	fn add(&mut self, other: &MemoryMapUsage) {
+		self.size += other.size;
+		self.rss += other.rss;
+		self.pss += other.pss;
+		self.shared_clean += other.shared_clean;
+		self.shared_dirty += other.shared_dirty;
+		self.private_clean += other.private_clean;
+		self.private_dirty += other.private_dirty;
+		self.referenced += other.referenced;
+		self.anonymous += other.anonymous;
+		self.swap += other.swap;
+	}
+}
+
+/// A single mapping of a process, as returned by `ProcessExt::memory_maps`.
+#[cfg_attr(feature = "serde", serde(crate = "renamed_serde"))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MemoryMap {
+	pub(crate) address: Range<u64>,
+	pub(crate) permissions: String,
+	pub(crate) offset: u64,
+	pub(crate) device: (u32, u32),
+	pub(crate) inode: u64,
+	pub(crate) path: PathBuf,
+	pub(crate) usage: MemoryMapUsage,
+}
+
+impl MemoryMap {
+	/// Renamed from `addr` in Python psutil.
+	pub fn address(&self) -> Range<u64> {
+		self.address.clone()
+	}
+
+	/// Renamed from `perms` in Python psutil.
+	/// For example `r-xp`, where the last character is `p` for private or `s` for shared.
+	pub fn permissions(&self) -> &str {
+		&self.permissions
+	}
+
+	/// New method, not in Python psutil.
+	pub fn offset(&self) -> u64 {
+		self.offset
+	}
+
+	/// New method, not in Python psutil.
+	/// Major and minor number of the device holding the mapped file.
+	pub fn device(&self) -> (u32, u32) {
+		self.device
+	}
+
+	/// New method, not in Python psutil.
+	pub fn inode(&self) -> u64 {
+		self.inode
+	}
+
+	/// The mapped file, a pseudo-path such as `[heap]` or `[stack]`, or `[anon]` for anonymous
+	/// mappings.
+	pub fn path(&self) -> &Path {
+		&self.path
+	}
+
+	pub fn usage(&self) -> &MemoryMapUsage {
+		&self.usage
+	}
+}
+
+/// The mappings of a process that share a path, as returned by
+/// `ProcessExt::memory_maps_grouped`.
+#[cfg_attr(feature = "serde", serde(crate = "renamed_serde"))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GroupedMemoryMap {
+	pub(crate) path: PathBuf,
+	pub(crate) usage: MemoryMapUsage,
+}
+
+impl GroupedMemoryMap {
+	pub fn path(&self) -> &Path {
+		&self.path
+	}
+
+	/// The usage of all the mappings summed together.
+	pub fn usage(&self) -> &MemoryMapUsage {
+		&self.usage
+	}
+}
+
+/// Splits off the first whitespace-separated field, returning it and the rest of the line.
+fn split_field(line: &str) -> (&str, &str) {
+	let line = line.trim_start();
+	let end = line.find(char::is_whitespace).unwrap_or(line.len());
+
+	line.split_at(end)
+}
+
+fn parse_header(line: &str, contents: &str) -> Result<MemoryMap> {
+	let parse_int = |err: std::num::ParseIntError| -> Error {
+		Error::ParseInt {
+			path: SMAPS.into(),
+			contents: contents.to_string(),
+			source: err,
+		}
+	};
+	let missing_data = || Error::MissingData {
+		path: SMAPS.into(),
+		contents: line.to_string(),
+	};
+
+	let (address, rest) = split_field(line);
+	let (permissions, rest) = split_field(rest);
+	let (offset, rest) = split_field(rest);
+	let (device, rest) = split_field(rest);
+	let (inode, rest) = split_field(rest);
+	// the path can contain spaces
+	let path = rest.trim();
+
+	let (start, end) = address.split_once('-').ok_or_else(missing_data)?;
+	let (major, minor) = device.split_once(':').ok_or_else(missing_data)?;
+	if permissions.is_empty() || inode.is_empty() {
+		return Err(missing_data());
+	}
+
+	Ok(MemoryMap {
+		address: u64::from_str_radix(start, 16).map_err(parse_int)?
+			..u64::from_str_radix(end, 16).map_err(parse_int)?,
+		permissions: permissions.to_string(),
+		offset: u64::from_str_radix(offset, 16).map_err(parse_int)?,
+		device: (
+			u32::from_str_radix(major, 16).map_err(parse_int)?,
+			u32::from_str_radix(minor, 16).map_err(parse_int)?,
+		),
+		inode: inode.parse().map_err(parse_int)?,
+		path: PathBuf::from(if path.is_empty() { ANONYMOUS } else { path }),
+		usage: MemoryMapUsage::default(),
+	})
+}
+
+pub(crate) fn parse_memory_maps(contents: &str) -> Result<Vec<MemoryMap>> {
+	let mut maps: Vec<MemoryMap> = Vec::new();
+
+	for line in contents.lines() {
+		let (first, _) = split_field(line);
+		if first.is_empty() {
+			continue;
+		}
+
+		// every field line starts with a key such as `Rss:`, everything else starts a new mapping
+		if !first.ends_with(':') {
+			maps.push(parse_header(line, contents)?);
+			continue;
+		}
+
+		let (key, value) = match parse_smaps_size(line, SMAPS, contents)? {
+			Some(size) => size,
+			None => continue,
+		};
+		let usage = match maps.last_mut() {
+			Some(map) => &mut map.usage,
+			None => {
+				return Err(Error::MissingData {
+					path: SMAPS.into(),
+					contents: contents.to_string(),
+				})
+			}
+		};
+
+		let field = match key {
+			"Size" => &mut usage.size,
+			"Rss" => &mut usage.rss,
+			"Pss" => &mut usage.pss,
+			"Shared_Clean" => &mut usage.shared_clean,
+			"Shared_Dirty" => &mut usage.shared_dirty,
+			"Private_Clean" => &mut usage.private_clean,
+			"Private_Dirty" => &mut usage.private_dirty,
+			"Referenced" => &mut usage.referenced,
+			"Anonymous" => &mut usage.anonymous,
+			"Swap" => &mut usage.swap,
+			_ => continue,
+		};
+		*field = value;
+	}
+
+	Ok(maps)
+}
+
+/// Groups the mappings by path, keeping the order in which each path first appears.
+pub(crate) fn group_memory_maps(maps: Vec<MemoryMap>) -> Vec<GroupedMemoryMap> {
+	let mut grouped: Vec<GroupedMemoryMap> = Vec::new();
+	let mut indices: HashMap<PathBuf, usize> = HashMap::new();
+
+	for map in maps {
+		match indices.get(&map.path) {
+			Some(&index) => grouped[index].usage.add(&map.usage),
+			None => {
+				indices.insert(map.path.clone(), grouped.len());
+				grouped.push(GroupedMemoryMap {
+					path: map.path,
+					usage: map.usage,
+				});
+			}
+		}
+	}
+
+	grouped
+}
+
+#[cfg(test)]
+mod unit_tests {
+	use super::*;
+
+	const CONTENTS: &str = "\
+55cf7aaf0000-55cf7aaf2000 r--p 00000000 fe:00 317783                     /usr/bin/my app
+Size:                  8 kB
+Rss:                   8 kB
+Pss:                   4 kB
+Shared_Clean:          8 kB
+Private_Dirty:         0 kB
+Swap:                  0 kB
+THPeligible:    0
+VmFlags: rd mr mw me dw sd
+55cf7aaf2000-55cf7aaf8000 r-xp 00002000 fe:00 317783                     /usr/bin/my app
+Size:                 24 kB
+Rss:                  20 kB
+Pss:                  10 kB
+Shared_Clean:         20 kB
+Private_Dirty:         0 kB
+Swap:                  0 kB
+VmFlags: rd ex mr mw me dw sd
+7f0e2c000000-7f0e2c021000 rw-p 00000000 00:00 0
+Size:                132 kB
+Rss:                  12 kB
+Pss:                  12 kB
+Shared_Clean:          0 kB
+Private_Dirty:        12 kB
+Swap:                  4 kB
+VmFlags: rd wr mr mw me nr
+";
+
+	#[test]
+	fn test_parse_memory_maps() {
+		let maps = parse_memory_maps(CONTENTS).unwrap();
+
+		assert_eq!(maps.len(), 3);
+		assert_eq!(maps[0].address(), 0x55cf7aaf0000..0x55cf7aaf2000);
+		assert_eq!(maps[1].permissions(), "r-xp");
+		assert_eq!(maps[1].offset(), 0x2000);
+		assert_eq!(maps[1].device(), (0xfe, 0));
+		assert_eq!(maps[1].inode(), 317783);
+		assert_eq!(maps[1].path(), Path::new("/usr/bin/my app"));
+		assert_eq!(maps[1].usage().rss(), 20 * 1024);
+		assert_eq!(maps[2].path(), Path::new(ANONYMOUS));
+		assert_eq!(maps[2].usage().private(), 12 * 1024);
+		assert_eq!(maps[2].usage().swap(), 4 * 1024);
+	}
+
+	#[test]
+	fn test_group_memory_maps() {
+		let grouped = group_memory_maps(parse_memory_maps(CONTENTS).unwrap());
+
+		assert_eq!(grouped.len(), 2);
+		assert_eq!(grouped[0].path(), Path::new("/usr/bin/my app"));
+		assert_eq!(grouped[0].usage().size(), 32 * 1024);
+		assert_eq!(grouped[0].usage().pss(), 14 * 1024);
+		assert_eq!(grouped[1].usage().rss(), 12 * 1024);
+	}
+}
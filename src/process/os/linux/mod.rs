@@ -1,9 +1,29 @@
+mod capability;
+mod cpu_set;
 mod cpu_times;
+mod fields;
+mod io_counters;
+mod io_priority;
+mod memory_map;
+mod namespace;
 mod oneshot;
 mod process;
 mod procfs;
+mod resource_limit;
+mod snapshot;
+mod thread;
 
+pub use capability::*;
+pub use cpu_set::*;
 pub use cpu_times::*;
+pub use fields::*;
+pub use io_counters::*;
+pub use io_priority::*;
+pub use memory_map::*;
+pub use namespace::*;
 pub use oneshot::*;
 pub use process::*;
 pub use procfs::*;
+pub use resource_limit::*;
+pub use snapshot::*;
+pub use thread::*;
@@ -0,0 +1,235 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use std::collections::{BTreeMap, HashMap};
+
+use crate::process::os::linux::ProcessExt;
+use crate::process::{procfs_path, psutil_error_to_process_error, Process, ProcessResult};
+use crate::{read_dir, read_file, read_link, Error, Pid, Result};
+
+/// A namespace type, see `namespaces(7)`.
+#[cfg_attr(feature = "serde", serde(crate = "renamed_serde"))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Namespace {
+	Cgroup,
+	Ipc,
+	Mnt,
+	Net,
+	Pid,
+	/// Since Linux 5.6.
+	Time,
+	User,
+	Uts,
+}
+
+impl Namespace {
+	pub const ALL: [Namespace; 8] = [
+		Namespace::Cgroup,
+		Namespace::Ipc,
+		Namespace::Mnt,
+		Namespace::Net,
+		Namespace::Pid,
+		Namespace::Time,
+		Namespace::User,
+		Namespace::Uts,
+	];
+
+	/// Name of the link in `/proc/[pid]/ns/`.
+	pub fn name(self) -> &'static str {
+		match self {
+			Namespace::Cgroup => "cgroup",
+			Namespace::Ipc => "ipc",
+			Namespace::Mnt => "mnt",
+			Namespace::Net => "net",
+			Namespace::Pid => "pid",
+			Namespace::Time => "time",
+			Namespace::User => "user",
+			Namespace::Uts => "uts",
+		}
+	}
+
+	fn from_name(name: &str) -> Option<Namespace> {
+		Namespace::ALL
+			.iter()
+			.copied()
+			.find(|namespace| namespace.name() == name)
+	}
+}
+
+/// Parses the target of a link in `/proc/[pid]/ns/`, such as `net:[4026531840]`.
+fn parse_namespace_link(link: &str) -> Result<u64> {
+	let inode = link
+		.split_once(":[")
+		.and_then(|(_name, rest)| rest.strip_suffix(']'))
+		.ok_or_else(|| Error::MissingData {
+			path: "ns".into(),
+			contents: link.to_string(),
+		})?;
+
+	inode.parse().map_err(|err| Error::ParseInt {
+		path: "ns".into(),
+		contents: link.to_string(),
+		source: err,
+	})
+}
+
+/// New function, not in Python psutil.
+/// The inode number of a namespace of the process, which is shared by processes in the same
+/// namespace.
+pub fn procfs_namespace(pid: Pid, namespace: Namespace) -> ProcessResult<u64> {
+	let path = procfs_path(pid, "ns").join(namespace.name());
+	let link = read_link(path).map_err(|e| psutil_error_to_process_error(e, pid))?;
+
+	parse_namespace_link(&link.to_string_lossy()).map_err(|e| psutil_error_to_process_error(e, pid))
+}
+
+/// New function, not in Python psutil.
+/// The inode numbers of all namespaces of the process that are supported by the kernel.
+pub fn procfs_namespaces(pid: Pid) -> ProcessResult<HashMap<Namespace, u64>> {
+	let mut namespaces = HashMap::new();
+
+	for entry in
+		read_dir(procfs_path(pid, "ns")).map_err(|e| psutil_error_to_process_error(e, pid))?
+	{
+		// skips `pid_for_children` and `time_for_children`
+		let namespace = match Namespace::from_name(&entry.file_name().to_string_lossy()) {
+			Some(namespace) => namespace,
+			None => continue,
+		};
+
+		namespaces.insert(namespace, procfs_namespace(pid, namespace)?);
+	}
+
+	Ok(namespaces)
+}
+
+/// Prefixes of the cgroup of a container, as created by the systemd cgroup driver.
+const CONTAINER_PREFIXES: [&str; 4] = ["docker-", "cri-containerd-", "crio-", "libpod-"];
+
+/// Finds a container ID in the contents of `/proc/[pid]/cgroup`.
+///
+/// Matches the last path component that is a 64 character hex ID, optionally wrapped in a
+/// systemd scope such as `docker-<id>.scope`, as used by docker, containerd, cri-o and podman.
+fn parse_container_id(contents: &str) -> Option<String> {
+	let is_id = |s: &str| s.len() == 64 && s.bytes().all(|b| b.is_ascii_hexdigit());
+
+	contents
+		.lines()
+		// `hierarchy-ID:controllers:path`
+		.filter_map(|line| line.splitn(3, ':').nth(2))
+		.flat_map(|path| path.split('/'))
+		.filter_map(|component| {
+			let component = component.strip_suffix(".scope").unwrap_or(component);
+			let id = CONTAINER_PREFIXES
+				.iter()
+				.find_map(|prefix| component.strip_prefix(prefix))
+				.unwrap_or(component);
+
+			Some(id).filter(|id| is_id(id))
+		})
+		.next_back()
+		.map(|id| id.to_string())
+}
+
+/// New function, not in Python psutil.
+///
+/// Guesses the ID of the container the process runs in from its cgroup. Returns `None` if the
+/// process does not seem to run in a docker, containerd, cri-o or podman container.
+pub fn procfs_container_id(pid: Pid) -> ProcessResult<Option<String>> {
+	let contents =
+		read_file(procfs_path(pid, "cgroup")).map_err(|e| psutil_error_to_process_error(e, pid))?;
+
+	Ok(parse_container_id(&contents))
+}
+
+/// New function, not in Python psutil.
+///
+/// Groups the processes by the inode number of one of their namespaces, for example the network
+/// namespace that is shared by the containers of a Kubernetes pod.
+/// Processes whose namespace cannot be read, e.g. due to permissions, are left out.
+pub fn group_by_namespace<I>(processes: I, namespace: Namespace) -> BTreeMap<u64, Vec<Process>>
+where
+	I: IntoIterator<Item = Process>,
+{
+	let mut groups: BTreeMap<u64, Vec<Process>> = BTreeMap::new();
+
+	for process in processes {
+		if let Ok(inode) = process.namespace(namespace) {
+			groups.entry(inode).or_default().push(process);
+		}
+	}
+
+	groups
+}
+
+#[cfg(test)]
+mod unit_tests {
+	use super::*;
+
+	#[test]
+	fn test_parse_namespace_link() {
+		assert_eq!(
+			parse_namespace_link("net:[4026531840]").unwrap(),
+			4026531840
+		);
+		assert!(parse_namespace_link("net:4026531840").is_err());
+	}
+
+	#[test]
+	fn test_parse_container_id() {
+		let id = "7be92808767a667f35c8505cbf40d14e931ef6db5b0210329cf193b15ba9d605";
+
+		assert_eq!(
+			parse_container_id(&format!("12:cpuset:/docker/{}\n", id)).as_deref(),
+			Some(id)
+		);
+		assert_eq!(
+			parse_container_id(&format!(
+				"0::/kubepods.slice/kubepods-besteffort.slice/kubepods-besteffort-pod1234.slice/cri-containerd-{}.scope\n",
+				id
+			))
+			.as_deref(),
+			Some(id)
+		);
+		assert_eq!(
+			parse_container_id(&format!(
+				"0::/kubepods/burstable/pod0b5e7f3c-1c5a-4d3e-8d2f-3c0a2f6b7e1d/{}\n",
+				id
+			))
+			.as_deref(),
+			Some(id)
+		);
+		assert_eq!(
+			parse_container_id(&format!("0::/machine.slice/crio-{}.scope\n", id)).as_deref(),
+			Some(id)
+		);
+		assert_eq!(
+			parse_container_id(&format!(
+				"0::/user.slice/user-1000.slice/user@1000.service/user.slice/libpod-{}.scope/container\n",
+				id
+			))
+			.as_deref(),
+			Some(id)
+		);
+		assert_eq!(
+			parse_container_id("0::/user.slice/user-1000.slice/session-2.scope\n"),
+			None
+		);
+	}
+
+	#[test]
+	fn test_procfs_namespaces() {
+		let pid = std::process::id();
+		let namespaces = procfs_namespaces(pid).unwrap();
+
+		assert_eq!(
+			namespaces[&Namespace::Net],
+			procfs_namespace(pid, Namespace::Net).unwrap()
+		);
+		assert!(procfs_container_id(pid).is_ok());
+
+		let groups = group_by_namespace(vec![Process::current().unwrap()], Namespace::Net);
+		assert_eq!(groups[&namespaces[&Namespace::Net]].len(), 1);
+	}
+}
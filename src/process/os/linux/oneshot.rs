@@ -1,6 +1,7 @@
 use std::time::Instant;
 
-use crate::process::{Process, ProcessCpuTimes};
+use crate::process::os::linux::{IoCounters, ProcfsStatus};
+use crate::process::{MemoryInfo, Process, ProcessCpuTimes};
 use crate::utils::duration_percent;
 use crate::Percent;
 
@@ -10,6 +11,15 @@ pub trait Oneshot {
 	fn cpu_times_oneshot(&self) -> ProcessCpuTimes;
 
 	fn cpu_percent_oneshot(&mut self) -> Percent;
+
+	/// `None` unless `/proc/[pid]/statm` was read by a `ProcessCollector`.
+	fn memory_info_oneshot(&self) -> Option<MemoryInfo>;
+
+	/// `None` unless `/proc/[pid]/status` was read by a `ProcessCollector`.
+	fn procfs_status_oneshot(&self) -> Option<&ProcfsStatus>;
+
+	/// `None` unless `/proc/[pid]/io` was read by a `ProcessCollector`.
+	fn io_counters_oneshot(&self) -> Option<IoCounters>;
 }
 
 impl Oneshot for Process {
@@ -43,4 +53,16 @@ impl Oneshot for Process {
 
 		percent
 	}
+
+	fn memory_info_oneshot(&self) -> Option<MemoryInfo> {
+		self.procfs_statm.clone().map(MemoryInfo::from)
+	}
+
+	fn procfs_status_oneshot(&self) -> Option<&ProcfsStatus> {
+		self.procfs_status.as_ref()
+	}
+
+	fn io_counters_oneshot(&self) -> Option<IoCounters> {
+		self.procfs_io.clone()
+	}
 }
@@ -1,9 +1,23 @@
 use std::collections::HashMap;
+use std::fs;
+use std::os::unix::io::{AsFd, BorrowedFd};
+use std::sync::Arc;
 
+use nix::libc;
+
+use crate::cgroup::{procfs_cgroups, ProcessCgroup};
 use crate::process::os::linux::{
-	procfs_stat, procfs_statm, procfs_status, ProcfsStat, ProcfsStatm, ProcfsStatus,
+	group_memory_maps, ioprio_get, ioprio_set, parse_memory_maps, prlimit, procfs_container_id,
+	procfs_io, procfs_limits, procfs_namespace, procfs_namespaces, procfs_stat, procfs_statm,
+	procfs_status, sched_getaffinity, sched_setaffinity, Capabilities, CpuSet, GroupedMemoryMap,
+	IoCounters, IoPriority, MemoryMap, Namespace, ProcessSnapshot, ProcfsFields, ProcfsStat,
+	ProcfsStatm, ProcfsStatus, Resource, ResourceLimit, SeccompMode,
+};
+use crate::process::os::unix::Uid;
+use crate::process::{
+	io_error_to_process_error, pidfd_open, psutil_error_to_process_error, Process, ProcessError,
+	ProcessResult,
 };
-use crate::process::{psutil_error_to_process_error, Process, ProcessResult};
 use crate::{read_file, Error, Result};
 
 fn parse_environ(contents: &str) -> Result<HashMap<String, String>> {
@@ -23,28 +37,125 @@ fn parse_environ(contents: &str) -> Result<HashMap<String, String>> {
 		.collect()
 }
 
-pub struct IoCounters {}
+/// Reads a file in `/proc/[pid]/` that only exists if the kernel was built with some option,
+/// returning `None` if it is missing but the process is still running.
+fn read_optional_file(process: &Process, name: &str) -> ProcessResult<Option<Vec<u8>>> {
+	match fs::read(process.procfs_path(name)) {
+		Ok(contents) => Ok(Some(contents)),
+		Err(e) if e.kind() == std::io::ErrorKind::NotFound && process.is_running() => Ok(None),
+		Err(e) => Err(io_error_to_process_error(e, process.pid())),
+	}
+}
+
+/// Parses the contents of `loginuid` or `sessionid`, which are `u32::MAX` when unset.
+fn parse_audit_id(contents: &str, name: &str) -> Result<Option<u32>> {
+	let id = contents.trim().parse().map_err(|err| Error::ParseInt {
+		path: name.into(),
+		contents: contents.to_string(),
+		source: err,
+	})?;
+
+	Ok(if id == u32::MAX { None } else { Some(id) })
+}
 
 pub trait ProcessExt {
 	fn environ(&self) -> ProcessResult<HashMap<String, String>>;
 
-	fn get_ionice(&self) -> i32;
+	fn get_ionice(&self) -> ProcessResult<IoPriority>;
+
+	/// Only applies to the main thread, other threads keep their priority.
+	/// Use `ThreadExt::set_ionice` to change the priority of other threads.
+	fn set_ionice(&self, priority: IoPriority) -> ProcessResult<()>;
+
+	/// Uses `prlimit(2)`, falling back to `/proc/[pid]/limits` when access is denied.
+	fn get_rlimit(&self, resource: Resource) -> ProcessResult<ResourceLimit>;
+
+	/// Preemptively checks if the process is still alive.
+	/// Uses `prlimit(2)`, which needs `CAP_SYS_RESOURCE` for processes of other users or to raise
+	/// the hard limit.
+	fn set_rlimit(&self, resource: Resource, limit: ResourceLimit) -> ProcessResult<()>;
+
+	fn io_counters(&self) -> ProcessResult<IoCounters>;
 
-	fn set_ionice(&self, nice: i32);
+	/// The affinity of the main thread, use `ThreadExt::get_cpu_affinity` for other threads.
+	fn get_cpu_affinity(&self) -> ProcessResult<CpuSet>;
 
-	fn get_rlimit(&self) -> i32;
+	/// Preemptively checks if the process is still alive.
+	/// Only applies to the main thread, other threads keep their affinity.
+	/// Use `ThreadExt::set_cpu_affinity` to change the affinity of other threads.
+	fn set_cpu_affinity(&self, cpus: &CpuSet) -> ProcessResult<()>;
 
-	fn set_rlimit(&self, nice: i32);
+	/// The CPU the process last ran on.
+	fn cpu_num(&self) -> ProcessResult<u32>;
 
-	fn io_counters(&self) -> IoCounters;
+	fn memory_maps(&self) -> ProcessResult<Vec<MemoryMap>>;
 
-	fn get_cpu_affinity(&self) -> i32;
+	/// Equivalent to `memory_maps(grouped=True)` in Python psutil.
+	fn memory_maps_grouped(&self) -> ProcessResult<Vec<GroupedMemoryMap>>;
 
-	fn set_cpu_affinity(&self, nice: i32);
+	/// New method, not in Python psutil.
+	/// The capability sets from `/proc/[pid]/status`.
+	fn capabilities(&self) -> ProcessResult<Capabilities>;
 
-	fn cpu_num(&self) -> i32;
+	/// New method, not in Python psutil.
+	/// Returns `None` if the kernel was built without seccomp.
+	fn seccomp(&self) -> ProcessResult<Option<SeccompMode>>;
 
-	fn memory_maps(&self);
+	/// New method, not in Python psutil.
+	/// Returns `None` before Linux 4.10.
+	fn no_new_privs(&self) -> ProcessResult<Option<bool>>;
+
+	/// New method, not in Python psutil.
+	/// The security context from `/proc/[pid]/attr/current`, such as an SELinux context or an
+	/// AppArmor profile. Returns `None` if no LSM provides one, or if the kernel was built without
+	/// `CONFIG_SECURITY`.
+	fn lsm_label(&self) -> ProcessResult<Option<String>>;
+
+	/// New method, not in Python psutil.
+	/// The UID the audit subsystem attributes the session to, or `None` if it is unset or the
+	/// kernel was built without `CONFIG_AUDIT`.
+	fn loginuid(&self) -> ProcessResult<Option<Uid>>;
+
+	/// New method, not in Python psutil.
+	/// The audit session ID, or `None` if it is unset or the kernel was built without
+	/// `CONFIG_AUDIT`.
+	fn sessionid(&self) -> ProcessResult<Option<u32>>;
+
+	/// New method, not in Python psutil.
+	/// The inode number of a namespace of the process, which is shared by processes in the same
+	/// namespace.
+	fn namespace(&self, namespace: Namespace) -> ProcessResult<u64>;
+
+	/// New method, not in Python psutil.
+	/// The inode numbers of all namespaces of the process that are supported by the kernel.
+	fn namespaces(&self) -> ProcessResult<HashMap<Namespace, u64>>;
+
+	/// New method, not in Python psutil.
+	/// The cgroups of the process in every v1 hierarchy and in the unified (v2) hierarchy.
+	/// Use `Cgroup::from_process_cgroups` to read their limits and usage.
+	fn cgroups(&self) -> ProcessResult<Vec<ProcessCgroup>>;
+
+	/// New method, not in Python psutil.
+	/// Guesses the ID of the docker, containerd, cri-o or podman container the process runs in
+	/// from its cgroup.
+	fn container_id(&self) -> ProcessResult<Option<String>>;
+
+	/// New method, not in Python psutil.
+	///
+	/// Opens a pidfd for the process, which is then used to send signals and to check whether the
+	/// process is running, without a window in which the PID can be reused by another process.
+	/// Each pidfd uses a file descriptor, which is shared between clones of the process.
+	/// Fails on kernels older than 5.3.
+	fn open_pidfd(&mut self) -> ProcessResult<()>;
+
+	/// New method, not in Python psutil.
+	/// The pidfd opened by `open_pidfd`, which becomes readable once the process exits.
+	fn pidfd(&self) -> Option<BorrowedFd<'_>>;
+
+	/// Reads the requested files in `/proc/[pid]/` once, equivalent to the `oneshot()` context
+	/// in Python psutil.
+	/// Fails if any of them cannot be read, e.g. `io` of processes of other users.
+	fn oneshot(&self, fields: ProcfsFields) -> ProcessResult<ProcessSnapshot>;
 
 	/// New method, not in Python psutil
 	fn procfs_stat(&self) -> ProcessResult<ProcfsStat>;
@@ -54,6 +165,9 @@ pub trait ProcessExt {
 
 	/// New method, not in Python psutil
 	fn procfs_status(&self) -> ProcessResult<ProcfsStatus>;
+
+	/// New method, not in Python psutil
+	fn procfs_limits(&self) -> ProcessResult<HashMap<Resource, ResourceLimit>>;
 }
 
 impl ProcessExt for Process {
@@ -64,40 +178,157 @@ impl ProcessExt for Process {
 		parse_environ(&contents).map_err(|e| psutil_error_to_process_error(e, self.pid))
 	}
 
-	fn get_ionice(&self) -> i32 {
-		todo!()
+	fn get_ionice(&self) -> ProcessResult<IoPriority> {
+		ioprio_get(self.pid)
+	}
+
+	fn set_ionice(&self, priority: IoPriority) -> ProcessResult<()> {
+		ioprio_set(self.pid, priority)
 	}
 
-	fn set_ionice(&self, _nice: i32) {
-		todo!()
+	fn get_rlimit(&self, resource: Resource) -> ProcessResult<ResourceLimit> {
+		match prlimit(self.pid, resource, None) {
+			Err(ProcessError::AccessDenied { .. }) => {
+				let mut limits = self.procfs_limits()?;
+				limits.remove(&resource).ok_or_else(|| {
+					psutil_error_to_process_error(
+						Error::MissingData {
+							path: self.procfs_path("limits"),
+							contents: format!("{:?}", resource),
+						},
+						self.pid,
+					)
+				})
+			}
+			result => result,
+		}
 	}
 
-	fn get_rlimit(&self) -> i32 {
-		todo!()
+	fn set_rlimit(&self, resource: Resource, limit: ResourceLimit) -> ProcessResult<()> {
+		if !self.is_running() {
+			return Err(ProcessError::NoSuchProcess { pid: self.pid });
+		}
+
+		prlimit(self.pid, resource, Some(limit)).map(|_| ())
 	}
 
-	fn set_rlimit(&self, _nice: i32) {
-		todo!()
+	fn io_counters(&self) -> ProcessResult<IoCounters> {
+		procfs_io(self.pid)
 	}
 
-	fn io_counters(&self) -> IoCounters {
-		todo!()
+	fn get_cpu_affinity(&self) -> ProcessResult<CpuSet> {
+		sched_getaffinity(self.pid)
 	}
 
-	fn get_cpu_affinity(&self) -> i32 {
-		todo!()
+	fn set_cpu_affinity(&self, cpus: &CpuSet) -> ProcessResult<()> {
+		if !self.is_running() {
+			return Err(ProcessError::NoSuchProcess { pid: self.pid });
+		}
+
+		sched_setaffinity(self.pid, cpus)
+	}
+
+	fn cpu_num(&self) -> ProcessResult<u32> {
+		Ok(self.procfs_stat()?.processor as u32)
+	}
+
+	fn memory_maps(&self) -> ProcessResult<Vec<MemoryMap>> {
+		let contents = read_file(self.procfs_path("smaps"))
+			.map_err(|e| psutil_error_to_process_error(e, self.pid))?;
+
+		parse_memory_maps(&contents).map_err(|e| psutil_error_to_process_error(e, self.pid))
 	}
 
-	fn set_cpu_affinity(&self, _nice: i32) {
-		todo!()
+	fn memory_maps_grouped(&self) -> ProcessResult<Vec<GroupedMemoryMap>> {
+		Ok(group_memory_maps(self.memory_maps()?))
 	}
 
-	fn cpu_num(&self) -> i32 {
-		todo!()
+	fn capabilities(&self) -> ProcessResult<Capabilities> {
+		Ok(Capabilities::from(&self.procfs_status()?))
 	}
 
-	fn memory_maps(&self) {
-		todo!()
+	fn seccomp(&self) -> ProcessResult<Option<SeccompMode>> {
+		Ok(self.procfs_status()?.seccomp)
+	}
+
+	fn no_new_privs(&self) -> ProcessResult<Option<bool>> {
+		Ok(self.procfs_status()?.no_new_privs)
+	}
+
+	fn lsm_label(&self) -> ProcessResult<Option<String>> {
+		match fs::read(self.procfs_path("attr/current")) {
+			Ok(contents) => {
+				let label = String::from_utf8_lossy(&contents)
+					.trim_end_matches(['\0', '\n'])
+					.to_string();
+
+				Ok(Some(label).filter(|label| !label.is_empty()))
+			}
+			// no LSM provides a label
+			Err(e) if e.raw_os_error() == Some(libc::EINVAL) => Ok(None),
+			// the kernel was built without `CONFIG_SECURITY`
+			Err(e) if e.kind() == std::io::ErrorKind::NotFound && self.is_running() => Ok(None),
+			Err(e) => Err(io_error_to_process_error(e, self.pid)),
+		}
+	}
+
+	fn loginuid(&self) -> ProcessResult<Option<Uid>> {
+		let contents = match read_optional_file(self, "loginuid")? {
+			Some(contents) => contents,
+			None => return Ok(None),
+		};
+
+		parse_audit_id(&String::from_utf8_lossy(&contents), "loginuid")
+			.map_err(|e| psutil_error_to_process_error(e, self.pid))
+	}
+
+	fn sessionid(&self) -> ProcessResult<Option<u32>> {
+		let contents = match read_optional_file(self, "sessionid")? {
+			Some(contents) => contents,
+			None => return Ok(None),
+		};
+
+		parse_audit_id(&String::from_utf8_lossy(&contents), "sessionid")
+			.map_err(|e| psutil_error_to_process_error(e, self.pid))
+	}
+
+	fn namespace(&self, namespace: Namespace) -> ProcessResult<u64> {
+		procfs_namespace(self.pid, namespace)
+	}
+
+	fn namespaces(&self) -> ProcessResult<HashMap<Namespace, u64>> {
+		procfs_namespaces(self.pid)
+	}
+
+	fn cgroups(&self) -> ProcessResult<Vec<ProcessCgroup>> {
+		procfs_cgroups(self.pid).map_err(|e| psutil_error_to_process_error(e, self.pid))
+	}
+
+	fn container_id(&self) -> ProcessResult<Option<String>> {
+		procfs_container_id(self.pid)
+	}
+
+	fn open_pidfd(&mut self) -> ProcessResult<()> {
+		if self.pidfd.is_some() {
+			return Ok(());
+		}
+
+		let pidfd = pidfd_open(self.pid).map_err(|e| io_error_to_process_error(e, self.pid))?;
+		// the PID may have been reused before the pidfd was opened
+		if !self.is_running() {
+			return Err(ProcessError::NoSuchProcess { pid: self.pid });
+		}
+		self.pidfd = Some(Arc::new(pidfd));
+
+		Ok(())
+	}
+
+	fn pidfd(&self) -> Option<BorrowedFd<'_>> {
+		self.pidfd.as_ref().map(|pidfd| pidfd.as_fd())
+	}
+
+	fn oneshot(&self, fields: ProcfsFields) -> ProcessResult<ProcessSnapshot> {
+		ProcessSnapshot::new(self, fields)
 	}
 
 	fn procfs_stat(&self) -> ProcessResult<ProcfsStat> {
@@ -111,6 +342,10 @@ impl ProcessExt for Process {
 	fn procfs_status(&self) -> ProcessResult<ProcfsStatus> {
 		procfs_status(self.pid)
 	}
+
+	fn procfs_limits(&self) -> ProcessResult<HashMap<Resource, ResourceLimit>> {
+		procfs_limits(self.pid)
+	}
 }
 
 #[cfg(test)]
@@ -125,4 +360,96 @@ mod unit_tests {
 		assert_eq!(env["rootmnt"], "/root");
 		assert_eq!(env["recovery"], "");
 	}
+
+	#[test]
+	fn test_parse_audit_id() {
+		assert_eq!(parse_audit_id("1000", "loginuid").unwrap(), Some(1000));
+		assert_eq!(parse_audit_id("4294967295", "loginuid").unwrap(), None);
+		assert!(parse_audit_id("", "sessionid").is_err());
+	}
+
+	#[test]
+	fn test_security() {
+		let process = Process::current().unwrap();
+		let capabilities = process.capabilities().unwrap();
+
+		assert!(capabilities
+			.effective
+			.iter()
+			.all(|capability| capabilities.permitted.contains(capability)));
+		assert!(process.lsm_label().is_ok());
+		assert!(process.loginuid().is_ok());
+		assert!(process.sessionid().is_ok());
+		// as if the kernel was built without the option that provides the file
+		assert_eq!(read_optional_file(&process, "missing").unwrap(), None);
+	}
+
+	#[test]
+	fn test_cpu_affinity() {
+		let process = Process::current().unwrap();
+		let cpus = process.get_cpu_affinity().unwrap();
+
+		process.set_cpu_affinity(&cpus).unwrap();
+		assert_eq!(process.get_cpu_affinity().unwrap(), cpus);
+		assert!(cpus.contains(process.cpu_num().unwrap()));
+	}
+
+	#[test]
+	fn test_pidfd() {
+		let mut child = std::process::Command::new("sleep")
+			.arg("10")
+			.spawn()
+			.unwrap();
+		let mut process = Process::new(child.id()).unwrap();
+
+		assert!(process.pidfd().is_none());
+		if process.open_pidfd().is_err() {
+			// pidfds are not supported by this kernel
+			child.kill().unwrap();
+			child.wait().unwrap();
+			return;
+		}
+		assert!(process.pidfd().is_some());
+		assert!(process.clone().pidfd().is_some());
+
+		process.kill().unwrap();
+		child.wait().unwrap();
+		assert!(!process.is_running());
+		assert!(matches!(
+			process.kill(),
+			Err(ProcessError::NoSuchProcess { .. })
+		));
+	}
+
+	#[test]
+	fn test_ionice() {
+		let process = Process::current().unwrap();
+		let priority = process.get_ionice().unwrap();
+
+		process.set_ionice(priority).unwrap();
+		assert_eq!(process.get_ionice().unwrap(), priority);
+	}
+
+	#[test]
+	fn test_rlimit() {
+		let process = Process::current().unwrap();
+		let limit = process.get_rlimit(Resource::Nofile).unwrap();
+
+		process.set_rlimit(Resource::Nofile, limit).unwrap();
+		assert_eq!(process.get_rlimit(Resource::Nofile).unwrap(), limit);
+		assert_eq!(process.procfs_limits().unwrap()[&Resource::Nofile], limit);
+	}
+
+	#[test]
+	fn test_memory_maps() {
+		let process = Process::current().unwrap();
+		let maps = process.memory_maps().unwrap();
+		let grouped = process.memory_maps_grouped().unwrap();
+
+		assert!(!maps.is_empty());
+		assert!(grouped.len() <= maps.len());
+		assert!(grouped
+			.iter()
+			.any(|map| map.path() == std::path::Path::new("[stack]")));
+	}
 }
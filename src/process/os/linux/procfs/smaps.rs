@@ -0,0 +1,192 @@
+use std::io;
+use std::str::FromStr;
+
+use crate::process::{procfs_path, psutil_error_to_process_error, ProcessResult};
+use crate::{read_file, Error, Pid, Result};
+
+const SMAPS: &str = "smaps";
+const SMAPS_ROLLUP: &str = "smaps_rollup";
+
+/// Memory usage of a process summed over all of its mappings, read from
+/// `/proc/[pid]/smaps_rollup` or `/proc/[pid]/smaps`.
+///
+/// New struct, not in Python psutil.
+#[derive(Clone, Debug, Default)]
+pub struct ProcfsSmaps {
+	/// Resident Set Size (bytes).
+	pub rss: u64,
+
+	/// Proportional Set Size (bytes).
+	pub pss: u64,
+
+	/// Clean shared pages (bytes).
+	pub shared_clean: u64,
+
+	/// Dirty shared pages (bytes).
+	pub shared_dirty: u64,
+
+	/// Clean private pages (bytes).
+	pub private_clean: u64,
+
+	/// Dirty private pages (bytes).
+	pub private_dirty: u64,
+
+	/// Pages marked as referenced or accessed (bytes).
+	pub referenced: u64,
+
+	/// Anonymous pages (bytes).
+	pub anonymous: u64,
+
+	/// Private hugetlbfs pages (bytes).
+	pub private_hugetlb: u64,
+
+	/// Anonymous memory that is swapped out (bytes).
+	pub swap: u64,
+
+	/// Proportional swap size (bytes).
+	pub swap_pss: u64,
+}
+
+impl ProcfsSmaps {
+	/// Unique Set Size: memory that would be freed if the process exited (bytes).
+	pub fn uss(&self) -> u64 {
+		self.private_clean + self.private_dirty + self.private_hugetlb
+	}
+}
+
+/// Parses a line such as `Private_Dirty:   104 kB`, returning the key and the value in bytes.
+/// Returns `None` for the mapping headers and for fields that are not sizes.
+pub(crate) fn parse_smaps_size<'a>(
+	line: &'a str,
+	path: &str,
+	contents: &str,
+) -> Result<Option<(&'a str, u64)>> {
+	let (key, value) = match line.split_once(':') {
+		Some((key, value)) => (key, value.trim()),
+		None => return Ok(None),
+	};
+
+	let value = match value.strip_suffix(" kB") {
+		Some(value) => value.trim(),
+		None => return Ok(None),
+	};
+
+	let value: u64 = value.parse().map_err(|err| Error::ParseInt {
+		path: path.into(),
+		contents: contents.to_string(),
+		source: err,
+	})?;
+
+	Ok(Some((key, value * 1024)))
+}
+
+impl ProcfsSmaps {
+	/// Sums the fields of every mapping, so this works for both `smaps` and `smaps_rollup`.
+	/// `path` is the name of the file that was read, used in errors.
+	fn parse(contents: &str, path: &str) -> Result<Self> {
+		let mut smaps = ProcfsSmaps::default();
+
+		for line in contents.lines() {
+			let (key, value) = match parse_smaps_size(line, path, contents)? {
+				Some(size) => size,
+				None => continue,
+			};
+
+			let field = match key {
+				"Rss" => &mut smaps.rss,
+				"Pss" => &mut smaps.pss,
+				"Shared_Clean" => &mut smaps.shared_clean,
+				"Shared_Dirty" => &mut smaps.shared_dirty,
+				"Private_Clean" => &mut smaps.private_clean,
+				"Private_Dirty" => &mut smaps.private_dirty,
+				"Referenced" => &mut smaps.referenced,
+				"Anonymous" => &mut smaps.anonymous,
+				"Private_Hugetlb" => &mut smaps.private_hugetlb,
+				"Swap" => &mut smaps.swap,
+				"SwapPss" => &mut smaps.swap_pss,
+				_ => continue,
+			};
+			*field += value;
+		}
+
+		Ok(smaps)
+	}
+}
+
+impl FromStr for ProcfsSmaps {
+	type Err = Error;
+
+	fn from_str(contents: &str) -> Result<Self> {
+		ProcfsSmaps::parse(contents, SMAPS)
+	}
+}
+
+/// New function, not in Python psutil.
+///
+/// Reads `/proc/[pid]/smaps_rollup`, falling back to summing `/proc/[pid]/smaps` on kernels
+/// older than 4.14.
+pub fn procfs_smaps(pid: Pid) -> ProcessResult<ProcfsSmaps> {
+	let (contents, path) = match read_file(procfs_path(pid, SMAPS_ROLLUP)) {
+		Ok(contents) => (contents, SMAPS_ROLLUP),
+		Err(Error::ReadFile { source, .. }) if source.kind() == io::ErrorKind::NotFound => {
+			let contents = read_file(procfs_path(pid, SMAPS))
+				.map_err(|e| psutil_error_to_process_error(e, pid))?;
+
+			(contents, SMAPS)
+		}
+		Err(e) => return Err(psutil_error_to_process_error(e, pid)),
+	};
+
+	ProcfsSmaps::parse(&contents, path).map_err(|e| psutil_error_to_process_error(e, pid))
+}
+
+#[cfg(test)]
+mod unit_tests {
+	use super::*;
+	use std::path::Path;
+
+	#[test]
+	fn test_parse_smaps() {
+		let contents = "\
+55840e1ac000-55840e1ae000 r--p 00000000 fd:01 1234                       /usr/bin/cat
+Size:                  8 kB
+Rss:                   8 kB
+Pss:                   4 kB
+Shared_Clean:          8 kB
+Private_Clean:         0 kB
+Private_Dirty:         0 kB
+Swap:                  0 kB
+THPeligible:    0
+VmFlags: rd mr mw me dw sd
+7ffd5f6bc000-7ffd5f6dd000 rw-p 00000000 00:00 0                          [stack]
+Size:                132 kB
+Rss:                  12 kB
+Pss:                  12 kB
+Shared_Clean:          0 kB
+Private_Clean:         0 kB
+Private_Dirty:        12 kB
+Swap:                  4 kB
+VmFlags: rd wr mr mw me gd ac
+";
+		let smaps = ProcfsSmaps::from_str(contents).unwrap();
+
+		assert_eq!(smaps.rss, 20 * 1024);
+		assert_eq!(smaps.pss, 16 * 1024);
+		assert_eq!(smaps.shared_clean, 8 * 1024);
+		assert_eq!(smaps.swap, 4 * 1024);
+		assert_eq!(smaps.uss(), 12 * 1024);
+
+		match ProcfsSmaps::parse("Rss:  x kB", SMAPS_ROLLUP) {
+			Err(Error::ParseInt { path, .. }) => assert_eq!(path, Path::new(SMAPS_ROLLUP)),
+			result => panic!("{:?}", result),
+		}
+	}
+
+	#[test]
+	fn test_procfs_smaps() {
+		let smaps = procfs_smaps(std::process::id()).unwrap();
+
+		assert!(smaps.rss > 0);
+		assert!(smaps.uss() <= smaps.rss);
+	}
+}
@@ -385,3 +385,12 @@ pub fn procfs_stat(pid: Pid) -> ProcessResult<ProcfsStat> {
 
 	ProcfsStat::from_str(&contents).map_err(|e| psutil_error_to_process_error(e, pid))
 }
+
+/// New function, not in Python psutil.
+/// Reads `/proc/[pid]/task/[tid]/stat`, errors refer to the thread ID.
+pub fn procfs_task_stat(pid: Pid, tid: Pid) -> ProcessResult<ProcfsStat> {
+	let contents = read_file(procfs_path(pid, &format!("task/{}/{}", tid, STAT)))
+		.map_err(|e| psutil_error_to_process_error(e, tid))?;
+
+	ProcfsStat::from_str(&contents).map_err(|e| psutil_error_to_process_error(e, tid))
+}
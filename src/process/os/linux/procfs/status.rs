@@ -1,20 +1,193 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 use std::collections::HashMap;
 use std::str::FromStr;
 
+use crate::process::os::linux::CpuSet;
 use crate::process::os::unix::{Gid, Uid};
-use crate::process::{procfs_path, psutil_error_to_process_error, ProcessResult};
-use crate::{read_file, Error, Pid, Result};
+use crate::process::{procfs_path, psutil_error_to_process_error, ProcessResult, Status};
+use crate::{read_file, Bytes, Count, Error, Pid, Result};
 
 const STATUS: &str = "status";
 
-// TODO: rest of the fields
+/// Seccomp mode of a process, see `seccomp(2)`.
+#[cfg_attr(feature = "serde", serde(crate = "renamed_serde"))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SeccompMode {
+	Disabled,
+	/// Only `read`, `write`, `_exit` and `sigreturn` are allowed.
+	Strict,
+	/// System calls are filtered by BPF programs.
+	Filter,
+}
+
 /// New struct, not in Python psutil.
+///
+/// Fields that are missing on older kernels, for kernel threads, or depending on the kernel
+/// configuration (e.g. the allowed CPUs and memory nodes without `CONFIG_CPUSETS`), are `None`.
 #[derive(Clone, Debug)]
 pub struct ProcfsStatus {
+	/// Filename of the executable, truncated to 15 bytes.
+	pub name: Option<String>,
+
+	/// File mode creation mask (since Linux 4.7).
+	pub umask: Option<u32>,
+
+	pub state: Option<Status>,
+
+	/// Thread group ID, which is the PID of the process.
+	pub tgid: Option<Pid>,
+
+	/// NUMA group ID (since Linux 3.13), 0 if there is none.
+	pub ngid: Option<Pid>,
+
+	/// Thread ID.
+	pub pid: Option<Pid>,
+
+	/// PID of the parent process, 0 if there is none.
+	pub ppid: Option<Pid>,
+
+	/// PID of the process tracing this process, 0 if it is not traced.
+	pub tracer_pid: Option<Pid>,
+
+	/// Real, effective, saved set, and filesystem UIDs.
 	pub uid: [Uid; 4],
 
+	/// Real, effective, saved set, and filesystem GIDs.
 	pub gid: [Gid; 4],
 
+	/// Number of allocated file descriptor slots.
+	pub fd_size: Option<Count>,
+
+	/// Supplementary groups.
+	pub groups: Option<Vec<Gid>>,
+
+	/// Thread group ID in each PID namespace, from the outermost (since Linux 4.1).
+	pub ns_tgid: Option<Vec<Pid>>,
+
+	/// Thread ID in each PID namespace, from the outermost (since Linux 4.1).
+	pub ns_pid: Option<Vec<Pid>>,
+
+	/// Process group ID in each PID namespace, from the outermost (since Linux 4.1).
+	pub ns_pgid: Option<Vec<Pid>>,
+
+	/// Session ID in each PID namespace, from the outermost (since Linux 4.1).
+	pub ns_sid: Option<Vec<Pid>>,
+
+	/// Whether the process is a kernel thread (since Linux 6.0).
+	pub kthread: Option<bool>,
+
+	/// Peak virtual memory size (bytes).
+	pub vm_peak: Option<Bytes>,
+
+	/// Virtual memory size (bytes).
+	pub vm_size: Option<Bytes>,
+
+	/// Locked memory size (bytes).
+	pub vm_lck: Option<Bytes>,
+
+	/// Pinned memory size (bytes).
+	pub vm_pin: Option<Bytes>,
+
+	/// Peak resident set size (bytes).
+	pub vm_hwm: Option<Bytes>,
+
+	/// Resident set size (bytes), the sum of `rss_anon`, `rss_file` and `rss_shmem`.
+	pub vm_rss: Option<Bytes>,
+
+	/// Resident anonymous memory (bytes).
+	pub rss_anon: Option<Bytes>,
+
+	/// Resident file mappings (bytes).
+	pub rss_file: Option<Bytes>,
+
+	/// Resident shared memory (bytes).
+	pub rss_shmem: Option<Bytes>,
+
+	/// Size of the data segment (bytes).
+	pub vm_data: Option<Bytes>,
+
+	/// Size of the stack (bytes).
+	pub vm_stk: Option<Bytes>,
+
+	/// Size of the text segment (bytes).
+	pub vm_exe: Option<Bytes>,
+
+	/// Size of shared library code (bytes).
+	pub vm_lib: Option<Bytes>,
+
+	/// Size of page table entries (bytes).
+	pub vm_pte: Option<Bytes>,
+
+	/// Swapped-out virtual memory (bytes).
+	pub vm_swap: Option<Bytes>,
+
+	/// Size of hugetlb memory (bytes).
+	pub hugetlb_pages: Option<Bytes>,
+
+	/// Whether the process is dumping core (since Linux 4.15).
+	pub core_dumping: Option<bool>,
+
+	/// Number of threads.
+	pub threads: Option<Count>,
+
+	/// Number of signals queued for the real user.
+	pub sig_queued: Option<u64>,
+
+	/// Limit on the number of queued signals.
+	pub sig_queue_limit: Option<u64>,
+
+	/// Mask of signals pending for the thread.
+	pub sig_pnd: Option<u64>,
+
+	/// Mask of signals pending for the process.
+	pub shd_pnd: Option<u64>,
+
+	/// Mask of blocked signals.
+	pub sig_blk: Option<u64>,
+
+	/// Mask of ignored signals.
+	pub sig_ign: Option<u64>,
+
+	/// Mask of caught signals.
+	pub sig_cgt: Option<u64>,
+
+	/// Mask of inheritable capabilities.
+	pub cap_inh: Option<u64>,
+
+	/// Mask of permitted capabilities.
+	pub cap_prm: Option<u64>,
+
+	/// Mask of effective capabilities.
+	pub cap_eff: Option<u64>,
+
+	/// Mask of the capability bounding set.
+	pub cap_bnd: Option<u64>,
+
+	/// Mask of ambient capabilities (since Linux 4.3).
+	pub cap_amb: Option<u64>,
+
+	/// Whether the `no_new_privs` bit is set (since Linux 4.10).
+	pub no_new_privs: Option<bool>,
+
+	/// Missing if the kernel was built without `CONFIG_SECCOMP`.
+	pub seccomp: Option<SeccompMode>,
+
+	/// Number of attached seccomp filters (since Linux 5.9).
+	pub seccomp_filters: Option<u64>,
+
+	/// Mitigation status of the speculative store bypass vulnerability (since Linux 4.17), such
+	/// as `thread vulnerable`.
+	pub speculation_store_bypass: Option<String>,
+
+	/// CPUs the process may run on.
+	pub cpus_allowed_list: Option<CpuSet>,
+
+	/// Memory nodes the process may allocate memory on.
+	pub mems_allowed_list: Option<Vec<u32>>,
+
 	/// Voluntary context switches.
 	pub voluntary_ctxt_switches: Option<u64>,
 
@@ -45,19 +218,40 @@ impl FromStr for ProcfsStatus {
 			})
 			.collect::<Result<HashMap<&str, &str>>>()?;
 
-		let parse_u32 = |s: &str| -> Result<u32> {
-			s.parse().map_err(|err| Error::ParseInt {
-				path: STATUS.into(),
-				contents: contents.to_string(),
-				source: err,
-			})
+		let parse_int_error = |err| Error::ParseInt {
+			path: STATUS.into(),
+			contents: contents.to_string(),
+			source: err,
 		};
-		let parse_u64 = |s: &str| -> Result<u64> {
-			s.parse().map_err(|err| Error::ParseInt {
-				path: STATUS.into(),
-				contents: contents.to_string(),
-				source: err,
-			})
+		let parse_u32 = |s: &str| -> Result<u32> { s.parse().map_err(parse_int_error) };
+		let parse_u64 = |s: &str| -> Result<u64> { s.parse().map_err(parse_int_error) };
+		let parse_octal =
+			|s: &str| -> Result<u32> { u32::from_str_radix(s, 8).map_err(parse_int_error) };
+		let parse_hex =
+			|s: &str| -> Result<u64> { u64::from_str_radix(s, 16).map_err(parse_int_error) };
+		let parse_bool = |s: &str| -> Result<bool> { Ok(parse_u32(s)? != 0) };
+		// sizes are always in kB
+		let parse_bytes = |s: &str| -> Result<Bytes> {
+			let kb = s
+				.strip_suffix("kB")
+				.ok_or_else(|| missing_status_data(contents))?;
+
+			Ok(parse_u64(kb.trim())? * 1024)
+		};
+		let parse_u32_vec =
+			|s: &str| -> Result<Vec<u32>> { s.split_whitespace().map(&parse_u32).collect() };
+		// such as `0-3,8,10-11`
+		let parse_list = |s: &str| -> Result<Vec<u32>> {
+			let mut list = Vec::new();
+
+			for range in s.split(',').filter(|range| !range.is_empty()) {
+				match range.split_once('-') {
+					Some((start, end)) => list.extend(parse_u32(start)?..=parse_u32(end)?),
+					None => list.push(parse_u32(range)?),
+				}
+			}
+
+			Ok(list)
 		};
 
 		let get = |key: &str| -> Result<&str> {
@@ -65,6 +259,9 @@ impl FromStr for ProcfsStatus {
 				.copied()
 				.ok_or_else(|| missing_status_data(contents))
 		};
+		let get_bytes = |key: &str| -> Result<Option<Bytes>> {
+			map.get(key).map(|entry| parse_bytes(entry)).transpose()
+		};
 
 		let uid_fields = match get("Uid")?.split_whitespace().collect::<Vec<_>>() {
 			fields if fields.len() >= 4 => Ok(fields),
@@ -90,6 +287,41 @@ impl FromStr for ProcfsStatus {
 			parse_u32(gid_fields[3])?,
 		];
 
+		// such as `S (sleeping)`
+		let state = map
+			.get("State")
+			.map(|entry| -> Result<Status> {
+				let state = entry
+					.split_whitespace()
+					.next()
+					.ok_or_else(|| missing_status_data(contents))?;
+
+				Ok(Status::from_str(state)?)
+			})
+			.transpose()?;
+
+		// such as `0/24002`
+		let sig_q = map
+			.get("SigQ")
+			.map(|entry| -> Result<(u64, u64)> {
+				let (queued, limit) = entry
+					.split_once('/')
+					.ok_or_else(|| missing_status_data(contents))?;
+
+				Ok((parse_u64(queued)?, parse_u64(limit)?))
+			})
+			.transpose()?;
+
+		let seccomp = map
+			.get("Seccomp")
+			.map(|entry| match *entry {
+				"0" => Ok(SeccompMode::Disabled),
+				"1" => Ok(SeccompMode::Strict),
+				"2" => Ok(SeccompMode::Filter),
+				_ => Err(missing_status_data(contents)),
+			})
+			.transpose()?;
+
 		let voluntary_ctxt_switches = map
 			.get("voluntary_ctxt_switches")
 			.map(|entry| -> Result<u64> { parse_u64(entry) })
@@ -100,8 +332,136 @@ impl FromStr for ProcfsStatus {
 			.transpose()?;
 
 		Ok(ProcfsStatus {
+			name: map.get("Name").map(|entry| entry.to_string()),
+			umask: map
+				.get("Umask")
+				.map(|entry| parse_octal(entry))
+				.transpose()?,
+			state,
+			tgid: map.get("Tgid").map(|entry| parse_u32(entry)).transpose()?,
+			ngid: map.get("Ngid").map(|entry| parse_u32(entry)).transpose()?,
+			pid: map.get("Pid").map(|entry| parse_u32(entry)).transpose()?,
+			ppid: map.get("PPid").map(|entry| parse_u32(entry)).transpose()?,
+			tracer_pid: map
+				.get("TracerPid")
+				.map(|entry| parse_u32(entry))
+				.transpose()?,
 			uid,
 			gid,
+			fd_size: map
+				.get("FDSize")
+				.map(|entry| parse_u64(entry))
+				.transpose()?,
+			groups: map
+				.get("Groups")
+				.map(|entry| parse_u32_vec(entry))
+				.transpose()?,
+			ns_tgid: map
+				.get("NStgid")
+				.map(|entry| parse_u32_vec(entry))
+				.transpose()?,
+			ns_pid: map
+				.get("NSpid")
+				.map(|entry| parse_u32_vec(entry))
+				.transpose()?,
+			ns_pgid: map
+				.get("NSpgid")
+				.map(|entry| parse_u32_vec(entry))
+				.transpose()?,
+			ns_sid: map
+				.get("NSsid")
+				.map(|entry| parse_u32_vec(entry))
+				.transpose()?,
+			kthread: map
+				.get("Kthread")
+				.map(|entry| parse_bool(entry))
+				.transpose()?,
+			vm_peak: get_bytes("VmPeak")?,
+			vm_size: get_bytes("VmSize")?,
+			vm_lck: get_bytes("VmLck")?,
+			vm_pin: get_bytes("VmPin")?,
+			vm_hwm: get_bytes("VmHWM")?,
+			vm_rss: get_bytes("VmRSS")?,
+			rss_anon: get_bytes("RssAnon")?,
+			rss_file: get_bytes("RssFile")?,
+			rss_shmem: get_bytes("RssShmem")?,
+			vm_data: get_bytes("VmData")?,
+			vm_stk: get_bytes("VmStk")?,
+			vm_exe: get_bytes("VmExe")?,
+			vm_lib: get_bytes("VmLib")?,
+			vm_pte: get_bytes("VmPTE")?,
+			vm_swap: get_bytes("VmSwap")?,
+			hugetlb_pages: get_bytes("HugetlbPages")?,
+			core_dumping: map
+				.get("CoreDumping")
+				.map(|entry| parse_bool(entry))
+				.transpose()?,
+			threads: map
+				.get("Threads")
+				.map(|entry| parse_u64(entry))
+				.transpose()?,
+			sig_queued: sig_q.map(|(queued, _)| queued),
+			sig_queue_limit: sig_q.map(|(_, limit)| limit),
+			sig_pnd: map
+				.get("SigPnd")
+				.map(|entry| parse_hex(entry))
+				.transpose()?,
+			shd_pnd: map
+				.get("ShdPnd")
+				.map(|entry| parse_hex(entry))
+				.transpose()?,
+			sig_blk: map
+				.get("SigBlk")
+				.map(|entry| parse_hex(entry))
+				.transpose()?,
+			sig_ign: map
+				.get("SigIgn")
+				.map(|entry| parse_hex(entry))
+				.transpose()?,
+			sig_cgt: map
+				.get("SigCgt")
+				.map(|entry| parse_hex(entry))
+				.transpose()?,
+			cap_inh: map
+				.get("CapInh")
+				.map(|entry| parse_hex(entry))
+				.transpose()?,
+			cap_prm: map
+				.get("CapPrm")
+				.map(|entry| parse_hex(entry))
+				.transpose()?,
+			cap_eff: map
+				.get("CapEff")
+				.map(|entry| parse_hex(entry))
+				.transpose()?,
+			cap_bnd: map
+				.get("CapBnd")
+				.map(|entry| parse_hex(entry))
+				.transpose()?,
+			cap_amb: map
+				.get("CapAmb")
+				.map(|entry| parse_hex(entry))
+				.transpose()?,
+			no_new_privs: map
+				.get("NoNewPrivs")
+				.map(|entry| parse_bool(entry))
+				.transpose()?,
+			seccomp,
+			seccomp_filters: map
+				.get("Seccomp_filters")
+				.map(|entry| parse_u64(entry))
+				.transpose()?,
+			speculation_store_bypass: map
+				.get("Speculation_Store_Bypass")
+				.map(|entry| entry.to_string()),
+			cpus_allowed_list: map
+				.get("Cpus_allowed_list")
+				.map(|entry| -> Result<CpuSet> { Ok(parse_list(entry)?.into_iter().collect()) })
+				.transpose()?,
+			mems_allowed_list: map
+				.get("Mems_allowed_list")
+				.map(|entry| parse_list(entry))
+				.transpose()?,
 			voluntary_ctxt_switches,
 			nonvoluntary_ctxt_switches,
 		})
@@ -115,3 +475,124 @@ pub fn procfs_status(pid: Pid) -> ProcessResult<ProcfsStatus> {
 
 	ProcfsStatus::from_str(&contents).map_err(|e| psutil_error_to_process_error(e, pid))
 }
+
+#[cfg(test)]
+mod unit_tests {
+	use super::*;
+	use crate::process::ProcessError;
+
+	#[test]
+	fn test_parse_status() {
+		let contents = "\
+Name:	cat
+Umask:	0022
+State:	R (running)
+Tgid:	30319
+Ngid:	0
+Pid:	30319
+PPid:	30311
+TracerPid:	0
+Uid:	1000	1000	1000	1000
+Gid:	100	100	100	100
+FDSize:	64
+Groups:	10 100 
+NStgid:	30319	1
+NSpid:	30319	1
+NSpgid:	30319	1
+NSsid:	30311	1
+VmPeak:	    2640 kB
+VmSize:	    2640 kB
+VmLck:	       0 kB
+VmPin:	       0 kB
+VmHWM:	    1256 kB
+VmRSS:	    1256 kB
+RssAnon:	     104 kB
+RssFile:	    1152 kB
+RssShmem:	       0 kB
+VmData:	     360 kB
+VmStk:	     132 kB
+VmExe:	      20 kB
+VmLib:	    1528 kB
+VmPTE:	      48 kB
+VmSwap:	       0 kB
+HugetlbPages:	       0 kB
+CoreDumping:	0
+Threads:	1
+SigQ:	0/24002
+SigPnd:	0000000000000000
+ShdPnd:	0000000000000000
+SigBlk:	0000000000010000
+SigIgn:	0000000000000000
+SigCgt:	0000000000000000
+CapInh:	0000000000000000
+CapPrm:	000001ffffffffff
+CapEff:	000001ffffffffff
+CapBnd:	000001ffffffffff
+CapAmb:	0000000000000000
+NoNewPrivs:	1
+Seccomp:	2
+Seccomp_filters:	1
+Speculation_Store_Bypass:	thread vulnerable
+Cpus_allowed:	f
+Cpus_allowed_list:	0-2,5
+Mems_allowed:	00000000,00000001
+Mems_allowed_list:	0
+voluntary_ctxt_switches:	3
+nonvoluntary_ctxt_switches:	4
+";
+		let status = ProcfsStatus::from_str(contents).unwrap();
+
+		assert_eq!(status.name.as_deref(), Some("cat"));
+		assert_eq!(status.umask, Some(0o022));
+		assert!(matches!(status.state, Some(Status::Running)));
+		assert_eq!(status.ppid, Some(30311));
+		assert_eq!(status.uid, [1000; 4]);
+		assert_eq!(status.groups, Some(vec![10, 100]));
+		assert_eq!(status.ns_pid, Some(vec![30319, 1]));
+		assert_eq!(status.kthread, None);
+		assert_eq!(status.vm_rss, Some(1256 * 1024));
+		assert_eq!(status.sig_queue_limit, Some(24002));
+		assert_eq!(status.sig_blk, Some(1 << 16));
+		assert_eq!(status.cap_eff, Some(0x1ff_ffff_ffff));
+		assert_eq!(status.no_new_privs, Some(true));
+		assert_eq!(status.seccomp, Some(SeccompMode::Filter));
+		assert_eq!(
+			status.speculation_store_bypass.as_deref(),
+			Some("thread vulnerable")
+		);
+		assert_eq!(
+			status.cpus_allowed_list.unwrap().iter().collect::<Vec<_>>(),
+			vec![0, 1, 2, 5]
+		);
+		assert_eq!(status.mems_allowed_list, Some(vec![0]));
+		assert_eq!(status.voluntary_ctxt_switches, Some(3));
+
+		// without `CONFIG_CPUSETS`, and with only the fields that were always parsed
+		let contents = "\
+Uid:	1000	1000	1000	1000
+Gid:	100	100	100	100
+";
+		let status = ProcfsStatus::from_str(contents).unwrap();
+		assert_eq!(status.uid, [1000; 4]);
+		assert_eq!(status.cpus_allowed_list, None);
+		assert_eq!(status.sig_queued, None);
+	}
+
+	#[test]
+	fn test_procfs_status() {
+		let status = procfs_status(std::process::id()).unwrap();
+
+		assert_eq!(status.tgid, Some(std::process::id()));
+		assert!(status.threads.unwrap() >= 1);
+		assert!(status.vm_rss.is_some());
+		assert!(!status.cpus_allowed_list.unwrap().is_empty());
+
+		// including kernel threads, which have no memory fields
+		for pid in crate::process::pids().unwrap() {
+			match procfs_status(pid) {
+				Ok(_) | Err(ProcessError::NoSuchProcess { .. }) => {}
+				Err(e) => panic!("{}", e),
+			}
+		}
+	}
+}
@@ -0,0 +1,299 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use std::collections::HashMap;
+use std::convert::{TryFrom, TryInto};
+use std::io;
+use std::ptr;
+
+use nix::errno::Errno;
+use nix::libc;
+
+use crate::process::{procfs_path, psutil_error_to_process_error, ProcessResult};
+use crate::{read_file, Error, Pid, Result};
+
+const LIMITS: &str = "limits";
+
+/// A resource whose usage can be limited, see `getrlimit(2)`.
+#[cfg_attr(feature = "serde", serde(crate = "renamed_serde"))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Resource {
+	/// `RLIMIT_AS`: size of the virtual memory (bytes).
+	As,
+	/// `RLIMIT_CORE`: size of core dumps (bytes).
+	Core,
+	/// `RLIMIT_CPU`: CPU time (seconds).
+	Cpu,
+	/// `RLIMIT_DATA`: size of the data segment (bytes).
+	Data,
+	/// `RLIMIT_FSIZE`: size of created files (bytes).
+	Fsize,
+	/// `RLIMIT_LOCKS`: number of file locks.
+	Locks,
+	/// `RLIMIT_MEMLOCK`: locked memory (bytes).
+	Memlock,
+	/// `RLIMIT_MSGQUEUE`: POSIX message queues (bytes).
+	Msgqueue,
+	/// `RLIMIT_NICE`: ceiling of the nice value, as `20 - nice`.
+	Nice,
+	/// `RLIMIT_NOFILE`: number of open file descriptors.
+	Nofile,
+	/// `RLIMIT_NPROC`: number of processes of the real user.
+	Nproc,
+	/// `RLIMIT_RSS`: resident set size (bytes), not enforced.
+	Rss,
+	/// `RLIMIT_RTPRIO`: ceiling of the real-time priority.
+	Rtprio,
+	/// `RLIMIT_RTTIME`: CPU time without blocking under real-time scheduling (microseconds).
+	Rttime,
+	/// `RLIMIT_SIGPENDING`: number of queued signals.
+	Sigpending,
+	/// `RLIMIT_STACK`: size of the main thread stack (bytes).
+	Stack,
+}
+
+impl Resource {
+	/// Name of the resource in `/proc/[pid]/limits`.
+	fn procfs_name(self) -> &'static str {
+		match self {
+			Resource::As => "Max address space",
+			Resource::Core => "Max core file size",
+			Resource::Cpu => "Max cpu time",
+			Resource::Data => "Max data size",
+			Resource::Fsize => "Max file size",
+			Resource::Locks => "Max file locks",
+			Resource::Memlock => "Max locked memory",
+			Resource::Msgqueue => "Max msgqueue size",
+			Resource::Nice => "Max nice priority",
+			Resource::Nofile => "Max open files",
+			Resource::Nproc => "Max processes",
+			Resource::Rss => "Max resident set",
+			Resource::Rtprio => "Max realtime priority",
+			Resource::Rttime => "Max realtime timeout",
+			Resource::Sigpending => "Max pending signals",
+			Resource::Stack => "Max stack size",
+		}
+	}
+
+	const ALL: [Resource; 16] = [
+		Resource::As,
+		Resource::Core,
+		Resource::Cpu,
+		Resource::Data,
+		Resource::Fsize,
+		Resource::Locks,
+		Resource::Memlock,
+		Resource::Msgqueue,
+		Resource::Nice,
+		Resource::Nofile,
+		Resource::Nproc,
+		Resource::Rss,
+		Resource::Rtprio,
+		Resource::Rttime,
+		Resource::Sigpending,
+		Resource::Stack,
+	];
+}
+
+/// The value of a soft or hard resource limit.
+#[cfg_attr(feature = "serde", serde(crate = "renamed_serde"))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Limit {
+	Unlimited,
+	Value(u64),
+}
+
+impl From<libc::rlim_t> for Limit {
+	// rlim_t is a u32 on some 32-bit targets
+	#[allow(clippy::unnecessary_cast)]
+	fn from(value: libc::rlim_t) -> Self {
+		if value == libc::RLIM_INFINITY {
+			Limit::Unlimited
+		} else {
+			Limit::Value(value as u64)
+		}
+	}
+}
+
+/// Fails with `EINVAL` if the value does not fit in a 32-bit `rlim_t`.
+impl TryFrom<Limit> for libc::rlim_t {
+	type Error = Errno;
+
+	fn try_from(limit: Limit) -> std::result::Result<Self, Self::Error> {
+		match limit {
+			Limit::Unlimited => Ok(libc::RLIM_INFINITY),
+			Limit::Value(value) => libc::rlim_t::try_from(value).map_err(|_| Errno::EINVAL),
+		}
+	}
+}
+
+/// Soft and hard limit of a resource.
+#[cfg_attr(feature = "serde", serde(crate = "renamed_serde"))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ResourceLimit {
+	pub soft: Limit,
+	pub hard: Limit,
+}
+
+fn parse_limits(contents: &str) -> Result<HashMap<Resource, ResourceLimit>> {
+	let missing_data = || Error::MissingData {
+		path: LIMITS.into(),
+		contents: contents.to_string(),
+	};
+	let parse = |s: &str| -> Result<Limit> {
+		if s == "unlimited" {
+			return Ok(Limit::Unlimited);
+		}
+
+		s.parse().map(Limit::Value).map_err(|err| Error::ParseInt {
+			path: LIMITS.into(),
+			contents: contents.to_string(),
+			source: err,
+		})
+	};
+
+	let mut limits = HashMap::new();
+
+	// skip the header
+	for line in contents.lines().skip(1) {
+		// the name contains single spaces and is padded with more spaces
+		let (name, rest) = line.split_once("  ").ok_or_else(missing_data)?;
+		let resource = match Resource::ALL
+			.iter()
+			.find(|resource| resource.procfs_name() == name)
+		{
+			Some(resource) => *resource,
+			None => continue,
+		};
+
+		let fields = match rest.split_whitespace().collect::<Vec<_>>() {
+			fields if fields.len() >= 2 => Ok(fields),
+			_ => Err(missing_data()),
+		}?;
+
+		limits.insert(
+			resource,
+			ResourceLimit {
+				soft: parse(fields[0])?,
+				hard: parse(fields[1])?,
+			},
+		);
+	}
+
+	Ok(limits)
+}
+
+/// New function, not in Python psutil.
+/// Reads all resource limits from `/proc/[pid]/limits`.
+pub fn procfs_limits(pid: Pid) -> ProcessResult<HashMap<Resource, ResourceLimit>> {
+	let contents =
+		read_file(procfs_path(pid, LIMITS)).map_err(|e| psutil_error_to_process_error(e, pid))?;
+
+	parse_limits(&contents).map_err(|e| psutil_error_to_process_error(e, pid))
+}
+
+/// Calls `prlimit(2)`, setting the limit if `new` is given and returning the old limit.
+pub(crate) fn prlimit(
+	pid: Pid,
+	resource: Resource,
+	new: Option<ResourceLimit>,
+) -> ProcessResult<ResourceLimit> {
+	let raw_resource = match resource {
+		Resource::As => libc::RLIMIT_AS,
+		Resource::Core => libc::RLIMIT_CORE,
+		Resource::Cpu => libc::RLIMIT_CPU,
+		Resource::Data => libc::RLIMIT_DATA,
+		Resource::Fsize => libc::RLIMIT_FSIZE,
+		Resource::Locks => libc::RLIMIT_LOCKS,
+		Resource::Memlock => libc::RLIMIT_MEMLOCK,
+		Resource::Msgqueue => libc::RLIMIT_MSGQUEUE,
+		Resource::Nice => libc::RLIMIT_NICE,
+		Resource::Nofile => libc::RLIMIT_NOFILE,
+		Resource::Nproc => libc::RLIMIT_NPROC,
+		Resource::Rss => libc::RLIMIT_RSS,
+		Resource::Rtprio => libc::RLIMIT_RTPRIO,
+		Resource::Rttime => libc::RLIMIT_RTTIME,
+		Resource::Sigpending => libc::RLIMIT_SIGPENDING,
+		Resource::Stack => libc::RLIMIT_STACK,
+	};
+
+	let new = new
+		.map(|limit| -> std::result::Result<_, Errno> {
+			Ok(libc::rlimit {
+				rlim_cur: limit.soft.try_into()?,
+				rlim_max: limit.hard.try_into()?,
+			})
+		})
+		.transpose()
+		.map_err(|e| psutil_error_to_process_error(e.into(), pid))?;
+	let mut old = libc::rlimit {
+		rlim_cur: 0,
+		rlim_max: 0,
+	};
+
+	let result = unsafe {
+		libc::prlimit(
+			pid as libc::pid_t,
+			raw_resource,
+			new.as_ref()
+				.map_or(ptr::null(), |new| new as *const libc::rlimit),
+			&mut old,
+		)
+	};
+	if result != 0 {
+		return Err(psutil_error_to_process_error(
+			io::Error::last_os_error().into(),
+			pid,
+		));
+	}
+
+	Ok(ResourceLimit {
+		soft: old.rlim_cur.into(),
+		hard: old.rlim_max.into(),
+	})
+}
+
+#[cfg(test)]
+mod unit_tests {
+	use super::*;
+
+	#[test]
+	fn test_parse_limits() {
+		let contents = "\
+Limit                     Soft Limit           Hard Limit           Units
+Max cpu time              unlimited            unlimited            seconds
+Max stack size            8388608              unlimited            bytes
+Max open files            1024                 524288               files
+Max nice priority         0                    0
+";
+		let limits = parse_limits(contents).unwrap();
+
+		assert_eq!(limits.len(), 4);
+		assert_eq!(
+			limits[&Resource::Cpu],
+			ResourceLimit {
+				soft: Limit::Unlimited,
+				hard: Limit::Unlimited
+			}
+		);
+		assert_eq!(limits[&Resource::Stack].soft, Limit::Value(8388608));
+		assert_eq!(limits[&Resource::Stack].hard, Limit::Unlimited);
+		assert_eq!(limits[&Resource::Nofile].hard, Limit::Value(524288));
+		assert_eq!(limits[&Resource::Nice].soft, Limit::Value(0));
+	}
+
+	#[test]
+	fn test_procfs_limits() {
+		let pid = std::process::id();
+		let limits = procfs_limits(pid).unwrap();
+
+		assert_eq!(limits.len(), Resource::ALL.len());
+		assert_eq!(
+			limits[&Resource::Nofile],
+			prlimit(pid, Resource::Nofile, None).unwrap()
+		);
+	}
+}
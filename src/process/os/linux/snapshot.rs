@@ -0,0 +1,208 @@
+use crate::process::os::linux::{
+	procfs_io, procfs_stat, procfs_statm, procfs_status, IoCounters, ProcfsFields, ProcfsStat,
+	ProcfsStatm, ProcfsStatus,
+};
+use crate::process::os::unix::{Gids, Uids};
+use crate::process::{MemoryInfo, Process, ProcessCpuTimes, ProcessError, ProcessResult, Status};
+use crate::{Count, Pid};
+
+/// New struct, not in Python psutil.
+///
+/// The contents of the files in `/proc/[pid]/` read by `ProcessExt::oneshot`.
+/// Every accessor returns `None` if the file it needs was not requested.
+#[derive(Clone, Debug)]
+pub struct ProcessSnapshot {
+	pub(crate) pid: Pid,
+	pub(crate) fields: ProcfsFields,
+	pub(crate) procfs_stat: Option<ProcfsStat>,
+	pub(crate) procfs_statm: Option<ProcfsStatm>,
+	pub(crate) procfs_status: Option<ProcfsStatus>,
+	pub(crate) procfs_io: Option<IoCounters>,
+	pub(crate) cmdline: Option<Option<Vec<String>>>,
+}
+
+impl ProcessSnapshot {
+	pub(crate) fn new(process: &Process, fields: ProcfsFields) -> ProcessResult<ProcessSnapshot> {
+		let pid = process.pid();
+
+		let procfs_stat = if fields.contains(ProcfsFields::STAT) {
+			let procfs_stat = procfs_stat(pid)?;
+			// the PID was reused by another process
+			if procfs_stat.starttime != process.create_time() {
+				return Err(ProcessError::NoSuchProcess { pid });
+			}
+
+			Some(procfs_stat)
+		} else {
+			None
+		};
+
+		let procfs_statm = if fields.contains(ProcfsFields::STATM) {
+			Some(procfs_statm(pid)?)
+		} else {
+			None
+		};
+
+		let procfs_status = if fields.contains(ProcfsFields::STATUS) {
+			Some(procfs_status(pid)?)
+		} else {
+			None
+		};
+
+		let procfs_io = if fields.contains(ProcfsFields::IO) {
+			Some(procfs_io(pid)?)
+		} else {
+			None
+		};
+
+		let cmdline = if fields.contains(ProcfsFields::CMDLINE) {
+			Some(process.cmdline_vec()?)
+		} else {
+			None
+		};
+
+		// the PID may have been reused by another process before or while reading
+		if !process.is_running() {
+			return Err(ProcessError::NoSuchProcess { pid });
+		}
+
+		Ok(ProcessSnapshot {
+			pid,
+			fields,
+			procfs_stat,
+			procfs_statm,
+			procfs_status,
+			procfs_io,
+			cmdline,
+		})
+	}
+
+	pub fn pid(&self) -> Pid {
+		self.pid
+	}
+
+	/// The files that were read.
+	pub fn fields(&self) -> ProcfsFields {
+		self.fields
+	}
+
+	pub fn procfs_stat(&self) -> Option<&ProcfsStat> {
+		self.procfs_stat.as_ref()
+	}
+
+	pub fn procfs_statm(&self) -> Option<&ProcfsStatm> {
+		self.procfs_statm.as_ref()
+	}
+
+	pub fn procfs_status(&self) -> Option<&ProcfsStatus> {
+		self.procfs_status.as_ref()
+	}
+
+	/// Needs `ProcfsFields::STAT`.
+	pub fn name(&self) -> Option<&str> {
+		self.procfs_stat.as_ref().map(|stat| stat.comm.as_str())
+	}
+
+	/// Needs `ProcfsFields::STAT`.
+	pub fn ppid(&self) -> Option<Option<Pid>> {
+		self.procfs_stat.as_ref().map(|stat| stat.ppid)
+	}
+
+	/// Needs `ProcfsFields::STAT`.
+	pub fn status(&self) -> Option<Status> {
+		self.procfs_stat.as_ref().map(|stat| stat.state)
+	}
+
+	/// Needs `ProcfsFields::STAT`.
+	pub fn cpu_times(&self) -> Option<ProcessCpuTimes> {
+		self.procfs_stat.as_ref().map(ProcessCpuTimes::from)
+	}
+
+	/// Needs `ProcfsFields::STAT`.
+	pub fn num_threads(&self) -> Option<Count> {
+		self.procfs_stat
+			.as_ref()
+			.map(|stat| stat.num_threads as Count)
+	}
+
+	/// Needs `ProcfsFields::STAT`.
+	pub fn nice(&self) -> Option<i32> {
+		self.procfs_stat.as_ref().map(|stat| stat.nice as i32)
+	}
+
+	/// Needs `ProcfsFields::STATM`.
+	pub fn memory_info(&self) -> Option<MemoryInfo> {
+		self.procfs_statm.clone().map(MemoryInfo::from)
+	}
+
+	/// Needs `ProcfsFields::STATUS`.
+	pub fn uids(&self) -> Option<Uids> {
+		self.procfs_status.clone().map(Uids::from)
+	}
+
+	/// Needs `ProcfsFields::STATUS`.
+	pub fn gids(&self) -> Option<Gids> {
+		self.procfs_status.clone().map(Gids::from)
+	}
+
+	/// The sum of voluntary and involuntary context switches, see `Process::num_ctx_switches`.
+	/// Needs `ProcfsFields::STATUS`.
+	pub fn num_ctx_switches(&self) -> Option<Count> {
+		let status = self.procfs_status.as_ref()?;
+
+		Some(status.voluntary_ctxt_switches? + status.nonvoluntary_ctxt_switches?)
+	}
+
+	/// Needs `ProcfsFields::IO`.
+	pub fn io_counters(&self) -> Option<IoCounters> {
+		self.procfs_io.clone()
+	}
+
+	/// The inner `None` is usually due to the process being a kernel thread.
+	/// Needs `ProcfsFields::CMDLINE`.
+	pub fn cmdline(&self) -> Option<Option<String>> {
+		self.cmdline_vec()
+			.map(|cmdline| cmdline.map(|cmdline| cmdline.join(" ")))
+	}
+
+	/// The inner `None` is usually due to the process being a kernel thread.
+	/// Needs `ProcfsFields::CMDLINE`.
+	pub fn cmdline_vec(&self) -> Option<Option<&[String]>> {
+		self.cmdline.as_ref().map(|cmdline| cmdline.as_deref())
+	}
+}
+
+#[cfg(test)]
+mod unit_tests {
+	use super::*;
+	use crate::process::os::linux::ProcessExt;
+
+	#[test]
+	fn test_oneshot() {
+		let process = Process::current().unwrap();
+		let snapshot = process
+			.oneshot(ProcfsFields::STAT | ProcfsFields::STATUS | ProcfsFields::CMDLINE)
+			.unwrap();
+
+		assert_eq!(snapshot.pid(), process.pid());
+		assert_eq!(snapshot.name().unwrap(), process.name().unwrap());
+		assert!(snapshot.num_threads().unwrap() >= 1);
+		assert!(snapshot.uids().is_some());
+		assert!(snapshot.num_ctx_switches().is_some());
+		assert!(snapshot.cmdline_vec().unwrap().is_some());
+		assert!(snapshot.memory_info().is_none());
+		assert!(snapshot.io_counters().is_none());
+
+		let mut child = std::process::Command::new("true").spawn().unwrap();
+		let exited = Process::new(child.id()).unwrap();
+		child.wait().unwrap();
+		assert!(matches!(
+			exited.oneshot(ProcfsFields::STATM),
+			Err(ProcessError::NoSuchProcess { .. })
+		));
+		assert!(matches!(
+			exited.oneshot(ProcfsFields::STAT | ProcfsFields::STATUS),
+			Err(ProcessError::NoSuchProcess { .. })
+		));
+	}
+}
@@ -0,0 +1,36 @@
+use crate::process::os::linux::{
+	ioprio_get, ioprio_set, sched_getaffinity, sched_setaffinity, CpuSet, IoPriority,
+};
+use crate::process::{ProcessResult, Thread};
+
+pub trait ThreadExt {
+	/// New method, not in Python psutil.
+	fn get_cpu_affinity(&self) -> ProcessResult<CpuSet>;
+
+	/// New method, not in Python psutil.
+	fn set_cpu_affinity(&self, cpus: &CpuSet) -> ProcessResult<()>;
+
+	/// New method, not in Python psutil.
+	fn get_ionice(&self) -> ProcessResult<IoPriority>;
+
+	/// New method, not in Python psutil.
+	fn set_ionice(&self, priority: IoPriority) -> ProcessResult<()>;
+}
+
+impl ThreadExt for Thread {
+	fn get_cpu_affinity(&self) -> ProcessResult<CpuSet> {
+		sched_getaffinity(self.id)
+	}
+
+	fn set_cpu_affinity(&self, cpus: &CpuSet) -> ProcessResult<()> {
+		sched_setaffinity(self.id, cpus)
+	}
+
+	fn get_ionice(&self) -> ProcessResult<IoPriority> {
+		ioprio_get(self.id)
+	}
+
+	fn set_ionice(&self, priority: IoPriority) -> ProcessResult<()> {
+		ioprio_set(self.id, priority)
+	}
+}
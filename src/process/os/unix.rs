@@ -1,12 +1,90 @@
 use crate::process::{Process, ProcessResult};
 use crate::Count;
 
+#[cfg(target_os = "linux")]
+use std::collections::HashMap;
+#[cfg(target_os = "linux")]
+use std::fs;
+#[cfg(target_os = "linux")]
+use std::os::unix::fs::{FileTypeExt, MetadataExt};
+#[cfg(target_os = "linux")]
+use std::sync::Mutex;
+
+#[cfg(target_os = "linux")]
+use nix::unistd::{self, User};
+#[cfg(target_os = "linux")]
+use once_cell::sync::Lazy;
+
 #[cfg(target_os = "linux")]
 use crate::process::os::linux::{ProcessExt as _, ProcfsStatus};
+#[cfg(target_os = "linux")]
+use crate::process::psutil_error_to_process_error;
+#[cfg(target_os = "linux")]
+use crate::read_dir;
 
 pub type Uid = u32;
 pub type Gid = u32;
 
+#[cfg(target_os = "linux")]
+static USERNAMES: Lazy<Mutex<HashMap<Uid, String>>> = Lazy::new(Default::default);
+
+#[cfg(target_os = "linux")]
+static TERMINALS: Lazy<Mutex<HashMap<u64, String>>> = Lazy::new(Default::default);
+
+/// Looks up the name of a user in the passwd database, falling back to the UID like Python psutil.
+/// Results are cached since the lookup can be slow, e.g. with NSS backed by LDAP.
+#[cfg(target_os = "linux")]
+pub(crate) fn username(uid: Uid) -> String {
+	let mut usernames = USERNAMES.lock().unwrap();
+
+	usernames
+		.entry(uid)
+		.or_insert_with(|| match User::from_uid(unistd::Uid::from_raw(uid)) {
+			Ok(Some(user)) => user.name,
+			_ => uid.to_string(),
+		})
+		.clone()
+}
+
+/// Maps the device numbers of the character devices in `/dev` and `/dev/pts` to their names.
+#[cfg(target_os = "linux")]
+fn terminal_map() -> HashMap<u64, String> {
+	let mut terminals = HashMap::new();
+
+	for entry in ["/dev/pts", "/dev"]
+		.iter()
+		.filter_map(|dir| fs::read_dir(dir).ok())
+		.flatten()
+		.filter_map(|entry| entry.ok())
+	{
+		let metadata = match entry.metadata() {
+			Ok(metadata) if metadata.file_type().is_char_device() => metadata,
+			_ => continue,
+		};
+		if let Ok(path) = entry.path().strip_prefix("/dev") {
+			terminals
+				.entry(metadata.rdev())
+				.or_insert_with(|| path.to_string_lossy().into_owned());
+		}
+	}
+
+	terminals
+}
+
+/// Finds the device in `/dev` or `/dev/pts` with the given device number.
+/// The devices are cached and only scanned again when a device number is not found,
+/// e.g. for a newly opened pseudo terminal.
+#[cfg(target_os = "linux")]
+fn terminal_name(tty_nr: u64) -> Option<String> {
+	let mut terminals = TERMINALS.lock().unwrap();
+
+	if !terminals.contains_key(&tty_nr) {
+		*terminals = terminal_map();
+	}
+
+	terminals.get(&tty_nr).cloned()
+}
+
 pub struct Uids {
 	pub real: Uid,
 	pub effective: Uid,
@@ -46,9 +124,11 @@ pub trait ProcessExt {
 
 	fn gids(&self) -> ProcessResult<Gids>;
 
-	fn terminal(&self) -> Option<String>;
+	/// The name of the controlling terminal relative to `/dev`, such as `pts/3`.
+	/// Returns `None` if the process has no controlling terminal.
+	fn terminal(&self) -> ProcessResult<Option<String>>;
 
-	fn num_fds(&self) -> Count;
+	fn num_fds(&self) -> ProcessResult<Count>;
 }
 
 impl ProcessExt for Process {
@@ -78,11 +158,64 @@ impl ProcessExt for Process {
 		}
 	}
 
-	fn terminal(&self) -> Option<String> {
-		todo!()
+	fn terminal(&self) -> ProcessResult<Option<String>> {
+		#[cfg(target_os = "linux")]
+		{
+			let tty_nr = self.procfs_stat()?.tty_nr;
+			if tty_nr == 0 {
+				return Ok(None);
+			}
+
+			Ok(terminal_name(tty_nr as u64))
+		}
+		#[cfg(not(any(target_os = "linux")))]
+		{
+			todo!()
+		}
 	}
 
-	fn num_fds(&self) -> Count {
-		todo!()
+	fn num_fds(&self) -> ProcessResult<Count> {
+		#[cfg(target_os = "linux")]
+		{
+			let fds = read_dir(self.procfs_path("fd"))
+				.map_err(|e| psutil_error_to_process_error(e, self.pid))?;
+
+			Ok(fds.len() as Count)
+		}
+		#[cfg(not(any(target_os = "linux")))]
+		{
+			todo!()
+		}
+	}
+}
+
+#[cfg(test)]
+mod unit_tests {
+	use super::*;
+
+	#[cfg(target_os = "linux")]
+	#[test]
+	fn test_username() {
+		let root = User::from_uid(unistd::Uid::from_raw(0)).unwrap().unwrap();
+
+		assert_eq!(username(0), root.name);
+		assert_eq!(username(Uid::MAX - 1), (Uid::MAX - 1).to_string());
+	}
+
+	#[cfg(target_os = "linux")]
+	#[test]
+	fn test_terminal_name() {
+		// /dev/null is 1:3
+		assert_eq!(terminal_name((1 << 8) | 3), Some("null".to_string()));
+	}
+
+	#[cfg(target_os = "linux")]
+	#[test]
+	fn test_num_fds() {
+		let process = Process::current().unwrap();
+		let _file = std::fs::File::open("/proc/self/stat").unwrap();
+
+		assert!(process.num_fds().unwrap() > 0);
+		assert!(process.terminal().is_ok());
 	}
 }
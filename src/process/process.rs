@@ -4,6 +4,7 @@
 use std::cmp;
 use std::hash::{Hash, Hasher};
 use std::path::PathBuf;
+use std::process::ExitStatus;
 use std::time::{Duration, Instant};
 
 use nix::sys::signal::{kill, Signal};
@@ -11,15 +12,23 @@ use nix::unistd;
 
 use crate::common::NetConnectionType;
 use crate::memory;
+use crate::network::NetConnection;
 use crate::process::{
-	psutil_error_to_process_error, MemType, MemoryInfo, OpenFile, ProcessCpuTimes, ProcessError,
-	ProcessResult, Status,
+	psutil_error_to_process_error, MemType, MemoryFullInfo, MemoryInfo, OpenFile, ProcessCpuTimes,
+	ProcessError, ProcessResult, ProcessTree, Status, Thread,
 };
 use crate::utils::duration_percent;
 use crate::{Count, Percent, Pid};
 
 #[cfg(target_os = "linux")]
-use crate::process::os::linux::ProcfsStat;
+use std::os::unix::io::OwnedFd;
+#[cfg(target_os = "linux")]
+use std::sync::Arc;
+
+#[cfg(target_os = "linux")]
+use crate::process::os::linux::{IoCounters, ProcfsStat, ProcfsStatm, ProcfsStatus};
+#[cfg(target_os = "linux")]
+use crate::process::{io_error_to_process_error, pidfd_exited, pidfd_send_signal};
 
 // #[cfg_attr(feature = "serde", serde(crate = "renamed_serde"))]
 // #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -36,6 +45,18 @@ pub struct Process {
 	// TODO: ignore debug for this field when the feature becomes available.
 	// https://github.com/rust-lang/rust/issues/37009
 	pub(crate) procfs_stat: ProcfsStat,
+
+	// Only filled by `ProcessCollector`, according to its fields.
+	#[cfg(target_os = "linux")]
+	pub(crate) procfs_statm: Option<ProcfsStatm>,
+	#[cfg(target_os = "linux")]
+	pub(crate) procfs_status: Option<ProcfsStatus>,
+	#[cfg(target_os = "linux")]
+	pub(crate) procfs_io: Option<IoCounters>,
+
+	// Shared between clones so that cloning cannot fail.
+	#[cfg(target_os = "linux")]
+	pub(crate) pidfd: Option<Arc<OwnedFd>>,
 }
 
 impl Process {
@@ -90,8 +111,26 @@ impl Process {
 		self.ppid()?.map(Process::new).transpose()
 	}
 
-	pub fn parents(&self) -> Option<Vec<Process>> {
-		self.sys_parents()
+	/// Returns the parent, grandparent, and so on, ending with the first process whose parent is
+	/// unknown or has exited. A parent created after its child has exited too and its PID was
+	/// reused by an unrelated process, so it ends the chain as well.
+	/// Preemptively checks if the process is still alive.
+	pub fn parents(&self) -> ProcessResult<Vec<Process>> {
+		let mut parents = Vec::new();
+		let mut parent = self
+			.parent()?
+			.filter(|parent| parent.create_time() <= self.create_time());
+
+		while let Some(process) = parent {
+			parent = match process.parent() {
+				Ok(parent) => parent.filter(|parent| parent.create_time() <= process.create_time()),
+				Err(ProcessError::NoSuchProcess { .. }) => None,
+				Err(e) => return Err(e),
+			};
+			parents.push(process);
+		}
+
+		Ok(parents)
 	}
 
 	pub fn status(&self) -> ProcessResult<Status> {
@@ -102,27 +141,33 @@ impl Process {
 		self.sys_cwd()
 	}
 
-	pub fn username(&self) -> String {
+	/// The name of the real user of the process, or the UID if it has no name.
+	pub fn username(&self) -> ProcessResult<String> {
 		self.sys_username()
 	}
 
-	pub fn get_nice(&self) -> i32 {
+	pub fn get_nice(&self) -> ProcessResult<i32> {
 		self.sys_get_nice()
 	}
 
-	pub fn set_nice(&self, nice: i32) {
+	/// Preemptively checks if the process is still alive.
+	/// Lowering the nice value usually needs `CAP_SYS_NICE`.
+	pub fn set_nice(&self, nice: i32) -> ProcessResult<()> {
 		self.sys_set_nice(nice)
 	}
 
-	pub fn num_ctx_switches(&self) -> Count {
+	/// The sum of voluntary and involuntary context switches.
+	/// Differs from Python psutil, which returns them separately.
+	pub fn num_ctx_switches(&self) -> ProcessResult<Count> {
 		self.sys_num_ctx_switches()
 	}
 
-	pub fn num_threads(&self) -> Count {
+	pub fn num_threads(&self) -> ProcessResult<Count> {
 		self.sys_num_threads()
 	}
 
-	pub fn threads(&self) {
+	/// Threads that exit while they are being listed are left out.
+	pub fn threads(&self) -> ProcessResult<Vec<Thread>> {
 		self.sys_threads()
 	}
 
@@ -157,7 +202,9 @@ impl Process {
 		self.sys_memory_info()
 	}
 
-	pub fn memory_full_info(&self) {
+	/// Reading USS, PSS and swap is considerably slower than `memory_info`, and usually needs
+	/// higher privileges for processes owned by other users.
+	pub fn memory_full_info(&self) -> ProcessResult<MemoryFullInfo> {
 		self.sys_memory_full_info()
 	}
 
@@ -178,27 +225,63 @@ impl Process {
 		Ok(percent as f32)
 	}
 
+	/// Compares the given type of process memory to the total physical memory.
 	pub fn memory_percent_with_type(&self, r#type: MemType) -> ProcessResult<Percent> {
-		self.sys_memory_percent_with_type(r#type)
+		let virtual_memory =
+			memory::virtual_memory().map_err(|e| psutil_error_to_process_error(e, self.pid))?;
+		let bytes = self.sys_memory_with_type(r#type)?;
+		let percent = (bytes as f64 / virtual_memory.total() as f64) * 100.0;
+
+		Ok(percent as f32)
 	}
 
-	pub fn children(&self) {
-		self.sys_children()
+	/// Returns the direct children of the process, or all of its descendants if `recursive` is
+	/// set.
+	/// Preemptively checks if the process is still alive.
+	pub fn children(&self, recursive: bool) -> ProcessResult<Vec<Process>> {
+		let tree = ProcessTree::new().map_err(|e| psutil_error_to_process_error(e, self.pid))?;
+
+		if tree.get(self.pid) != Some(self) {
+			return Err(ProcessError::NoSuchProcess { pid: self.pid });
+		}
+
+		let children = if recursive {
+			tree.descendants(self.pid)
+		} else {
+			tree.children(self.pid)
+		};
+
+		Ok(children.into_iter().cloned().collect())
 	}
 
 	pub fn open_files(&self) -> ProcessResult<Vec<OpenFile>> {
 		self.sys_open_files()
 	}
 
-	pub fn connections(&self) {
+	/// Returns the inet sockets opened by the process.
+	pub fn connections(&self) -> ProcessResult<Vec<NetConnection>> {
 		self.sys_connections()
 	}
 
-	pub fn connections_with_type(&self, type_: NetConnectionType) {
+	pub fn connections_with_type(
+		&self,
+		type_: NetConnectionType,
+	) -> ProcessResult<Vec<NetConnection>> {
 		self.sys_connections_with_type(type_)
 	}
 
+	/// On Linux, a process with a pidfd (see `ProcessExt::open_pidfd`) that has not exited is known to
+	/// be running without comparing create times.
 	pub fn is_running(&self) -> bool {
+		#[cfg(target_os = "linux")]
+		{
+			if let Some(pidfd) = &self.pidfd {
+				if let Ok(false) = pidfd_exited(pidfd) {
+					return true;
+				}
+			}
+		}
+
 		match Process::new(self.pid) {
 			Ok(p) => p == *self,
 			Err(_) => false,
@@ -225,7 +308,17 @@ impl Process {
 	}
 
 	/// Preemptively checks if the process is still alive.
+	/// On Linux, a process with a pidfd (see `ProcessExt::open_pidfd`) is signalled through the
+	/// pidfd instead, so the signal cannot reach a different process that reused the PID.
 	pub fn send_signal(&self, signal: Signal) -> ProcessResult<()> {
+		#[cfg(target_os = "linux")]
+		{
+			if let Some(pidfd) = &self.pidfd {
+				return pidfd_send_signal(pidfd, signal)
+					.map_err(|e| io_error_to_process_error(e, self.pid));
+			}
+		}
+
 		if !self.is_running() {
 			return Err(ProcessError::NoSuchProcess { pid: self.pid });
 		}
@@ -285,8 +378,14 @@ impl Process {
 		}
 	}
 
-	pub fn wait(&self) {
-		self.sys_wait()
+	/// Waits for the process to exit, or until the timeout expires.
+	///
+	/// Returns the exit status if the process is a child of the current process, which also reaps
+	/// it. The exit status of other processes cannot be retrieved, so `None` is returned instead,
+	/// as it is if the process is already gone.
+	/// Fails with `ProcessError::TimeoutExpired` if the process is still running after the timeout.
+	pub fn wait(&self, timeout: Option<Duration>) -> ProcessResult<Option<ExitStatus>> {
+		self.sys_wait(timeout)
 	}
 }
 
@@ -337,6 +436,128 @@ mod unit_tests {
 		assert_ne!(Process::current().unwrap(), Process::new(1).unwrap());
 	}
 
+	#[test]
+	fn test_process_connections() {
+		let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+		let local_addr = crate::network::ConnectionAddress::Inet(listener.local_addr().unwrap());
+
+		let process = Process::current().unwrap();
+		assert!(process
+			.connections()
+			.unwrap()
+			.iter()
+			.any(|connection| connection.local_addr() == &local_addr));
+		assert!(!process
+			.connections_with_type(NetConnectionType::Udp)
+			.unwrap()
+			.iter()
+			.any(|connection| connection.local_addr() == &local_addr));
+	}
+
+	#[test]
+	fn test_process_wait() {
+		let mut command = std::process::Command::new("sh");
+		let mut child = command.args(["-c", "exit 3"]).spawn().unwrap();
+		let process = Process::new(child.id()).unwrap();
+
+		let status = process.wait(None).unwrap().unwrap();
+		assert_eq!(status.code(), Some(3));
+		assert!(!process.is_running());
+		// already reaped
+		assert!(child.try_wait().is_err());
+	}
+
+	#[test]
+	fn test_process_wait_timeout() {
+		let mut child = std::process::Command::new("sleep")
+			.arg("10")
+			.spawn()
+			.unwrap();
+		let process = Process::new(child.id()).unwrap();
+
+		assert!(matches!(
+			process.wait(Some(Duration::from_millis(50))),
+			Err(ProcessError::TimeoutExpired { .. })
+		));
+
+		process.kill().unwrap();
+		assert!(process
+			.wait(Some(Duration::from_secs(5)))
+			.unwrap()
+			.is_some());
+		assert!(child.try_wait().is_err());
+	}
+
+	#[test]
+	fn test_process_parents() {
+		let process = Process::current().unwrap();
+		let parents = process.parents().unwrap();
+		if let Some(parent) = parents.first() {
+			assert_eq!(Some(parent.pid()), process.ppid().unwrap());
+		}
+	}
+
+	#[test]
+	fn test_process_memory_full_info() {
+		let process = Process::current().unwrap();
+		let memory_full_info = process.memory_full_info().unwrap();
+
+		assert!(memory_full_info.uss() > 0);
+		assert!(memory_full_info.uss() <= memory_full_info.memory_info().rss());
+
+		let rss = process.memory_percent_with_type(MemType::Rss).unwrap();
+		let uss = process.memory_percent_with_type(MemType::Uss).unwrap();
+		assert!(rss > 0.0);
+		assert!(uss <= rss);
+	}
+
+	#[test]
+	fn test_process_nice() {
+		let process = Process::current().unwrap();
+		let nice = process.get_nice().unwrap();
+
+		process.set_nice(nice).unwrap();
+		assert_eq!(process.get_nice().unwrap(), nice);
+	}
+
+	#[test]
+	fn test_process_username() {
+		assert!(!Process::current().unwrap().username().unwrap().is_empty());
+	}
+
+	#[test]
+	fn test_process_num_ctx_switches() {
+		assert!(Process::current().unwrap().num_ctx_switches().unwrap() > 0);
+	}
+
+	#[test]
+	fn test_process_threads() {
+		let process = Process::current().unwrap();
+		let threads = process.threads().unwrap();
+
+		assert!(!threads.is_empty());
+		assert!(process.num_threads().unwrap() >= 1);
+		assert!(threads.iter().any(|thread| thread.id() == process.pid()));
+	}
+
+	#[test]
+	fn test_process_children() {
+		let mut child = std::process::Command::new("sleep")
+			.arg("10")
+			.spawn()
+			.unwrap();
+
+		let process = Process::current().unwrap();
+		let children = process.children(false).unwrap();
+		let descendants = process.children(true).unwrap();
+
+		child.kill().unwrap();
+		child.wait().unwrap();
+
+		assert!(children.iter().any(|p| p.pid() == child.id()));
+		assert!(descendants.iter().any(|p| p.pid() == child.id()));
+	}
+
 	#[test]
 	fn test_processes() {
 		processes().unwrap();
@@ -1,6 +1,10 @@
+mod pidfd;
 mod pids;
 mod process;
 mod status;
+mod thread;
+mod wait;
 
+pub(crate) use pidfd::*;
 pub use pids::*;
 pub use process::*;
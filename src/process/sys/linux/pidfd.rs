@@ -0,0 +1,79 @@
+use std::cmp;
+use std::io;
+use std::os::unix::io::{AsRawFd, FromRawFd, OwnedFd};
+use std::ptr;
+use std::time::{Duration, Instant};
+
+use nix::libc;
+use nix::sys::signal::Signal;
+
+use crate::Pid;
+
+/// Opens a pidfd for the process. Fails with `ENOSYS` on kernels older than 5.3.
+pub(crate) fn pidfd_open(pid: Pid) -> io::Result<OwnedFd> {
+	let fd = unsafe { libc::syscall(libc::SYS_pidfd_open, pid as libc::pid_t, 0) };
+	if fd < 0 {
+		return Err(io::Error::last_os_error());
+	}
+
+	Ok(unsafe { OwnedFd::from_raw_fd(fd as libc::c_int) })
+}
+
+/// Sends a signal to the process referred to by the pidfd, which cannot be a reused PID.
+pub(crate) fn pidfd_send_signal(pidfd: &OwnedFd, signal: Signal) -> io::Result<()> {
+	let result = unsafe {
+		libc::syscall(
+			libc::SYS_pidfd_send_signal,
+			pidfd.as_raw_fd(),
+			signal as libc::c_int,
+			ptr::null::<libc::siginfo_t>(),
+			0,
+		)
+	};
+	if result < 0 {
+		return Err(io::Error::last_os_error());
+	}
+
+	Ok(())
+}
+
+/// Returns `true` if the process referred to by the pidfd has exited, without waiting.
+pub(crate) fn pidfd_exited(pidfd: &OwnedFd) -> io::Result<bool> {
+	poll_pidfd(pidfd, Some(Instant::now()))
+}
+
+pub(crate) fn remaining(deadline: Option<Instant>) -> Option<Duration> {
+	deadline.map(|deadline| deadline.saturating_duration_since(Instant::now()))
+}
+
+/// Waits until the pidfd is readable, which happens once the process has exited.
+/// Returns `false` if the deadline passed first.
+pub(crate) fn poll_pidfd(pidfd: &OwnedFd, deadline: Option<Instant>) -> io::Result<bool> {
+	let mut poll_fd = libc::pollfd {
+		fd: pidfd.as_raw_fd(),
+		events: libc::POLLIN,
+		revents: 0,
+	};
+
+	loop {
+		let timeout = match remaining(deadline) {
+			// round up so that we don't wake up just before the deadline
+			Some(remaining) => cmp::min(
+				remaining.as_nanos().div_ceil(1_000_000),
+				libc::c_int::MAX as u128,
+			) as libc::c_int,
+			None => -1,
+		};
+
+		match unsafe { libc::poll(&mut poll_fd, 1, timeout) } {
+			-1 => {
+				let e = io::Error::last_os_error();
+				if e.kind() != io::ErrorKind::Interrupted {
+					return Err(e);
+				}
+			}
+			0 => return Ok(false),
+			_ => return Ok(true),
+		}
+	}
+}
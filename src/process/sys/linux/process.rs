@@ -2,13 +2,21 @@ use std::path::PathBuf;
 use std::string::ToString;
 use std::time::Instant;
 
+use nix::errno::Errno;
+use nix::libc;
+
 use crate::common::NetConnectionType;
-use crate::process::os::linux::{procfs_stat, ProcessExt as _};
+use crate::network::{procfs_net_connections, socket_inodes, NetConnection};
+use crate::process::os::linux::{
+	procfs_io, procfs_smaps, procfs_stat, procfs_statm, procfs_status, ProcessExt as _,
+	ProcfsFields,
+};
+use crate::process::os::unix::{username, ProcessExt as _};
 use crate::process::{
-	pids, psutil_error_to_process_error, MemType, MemoryInfo, OpenFile, Process, ProcessCpuTimes,
-	ProcessResult, Status,
+	pids, psutil_error_to_process_error, MemType, MemoryFullInfo, MemoryInfo, OpenFile, Process,
+	ProcessCpuTimes, ProcessError, ProcessResult, Status, Thread,
 };
-use crate::{read_dir, read_file, read_link, Count, Percent, Pid, Result};
+use crate::{read_dir, read_file, read_link, Bytes, Count, Error, Pid, Result};
 
 /// Returns a path to a file in `/proc/[pid]/`.
 pub(crate) fn procfs_path(pid: Pid, name: &str) -> PathBuf {
@@ -29,9 +37,27 @@ impl Process {
 			busy,
 			instant,
 			procfs_stat,
+			procfs_statm: None,
+			procfs_status: None,
+			procfs_io: None,
+			pidfd: None,
 		})
 	}
 
+	/// Rereads the cached files in `fields`, keeping the others.
+	/// Files that cannot be read, e.g. due to permissions, are cleared.
+	pub(crate) fn refresh_procfs(&mut self, fields: ProcfsFields) {
+		if fields.contains(ProcfsFields::STATM) {
+			self.procfs_statm = procfs_statm(self.pid).ok();
+		}
+		if fields.contains(ProcfsFields::STATUS) {
+			self.procfs_status = procfs_status(self.pid).ok();
+		}
+		if fields.contains(ProcfsFields::IO) {
+			self.procfs_io = procfs_io(self.pid).ok();
+		}
+	}
+
 	pub(crate) fn procfs_path(&self, name: &str) -> PathBuf {
 		procfs_path(self.pid, name)
 	}
@@ -68,10 +94,6 @@ impl Process {
 		Ok(Some(split))
 	}
 
-	pub(crate) fn sys_parents(&self) -> Option<Vec<Process>> {
-		todo!()
-	}
-
 	pub(crate) fn sys_status(&self) -> ProcessResult<Status> {
 		Ok(self.procfs_stat()?.state)
 	}
@@ -80,28 +102,68 @@ impl Process {
 		read_link(self.procfs_path("cwd")).map_err(|e| psutil_error_to_process_error(e, self.pid))
 	}
 
-	pub(crate) fn sys_username(&self) -> String {
-		todo!()
+	pub(crate) fn sys_username(&self) -> ProcessResult<String> {
+		Ok(username(self.uids()?.real))
 	}
 
-	pub(crate) fn sys_get_nice(&self) -> i32 {
-		todo!()
+	pub(crate) fn sys_get_nice(&self) -> ProcessResult<i32> {
+		Ok(self.procfs_stat()?.nice as i32)
 	}
 
-	pub(crate) fn sys_set_nice(&self, _nice: i32) {
-		todo!()
+	pub(crate) fn sys_set_nice(&self, nice: i32) -> ProcessResult<()> {
+		if !self.is_running() {
+			return Err(ProcessError::NoSuchProcess { pid: self.pid });
+		}
+
+		let result = unsafe { libc::setpriority(libc::PRIO_PROCESS, self.pid as libc::id_t, nice) };
+
+		Errno::result(result)
+			.map(|_| ())
+			.map_err(|e| psutil_error_to_process_error(e.into(), self.pid))
 	}
 
-	pub(crate) fn sys_num_ctx_switches(&self) -> Count {
-		todo!()
+	pub(crate) fn sys_num_ctx_switches(&self) -> ProcessResult<Count> {
+		let procfs_status = self.procfs_status()?;
+
+		match (
+			procfs_status.voluntary_ctxt_switches,
+			procfs_status.nonvoluntary_ctxt_switches,
+		) {
+			(Some(voluntary), Some(nonvoluntary)) => Ok(voluntary + nonvoluntary),
+			_ => Err(psutil_error_to_process_error(
+				Error::MissingData {
+					path: self.procfs_path("status"),
+					contents: "ctxt_switches".to_string(),
+				},
+				self.pid,
+			)),
+		}
 	}
 
-	pub(crate) fn sys_num_threads(&self) -> Count {
-		todo!()
+	pub(crate) fn sys_num_threads(&self) -> ProcessResult<Count> {
+		Ok(self.procfs_stat()?.num_threads as Count)
 	}
 
-	pub(crate) fn sys_threads(&self) {
-		todo!()
+	pub(crate) fn sys_threads(&self) -> ProcessResult<Vec<Thread>> {
+		let mut threads = Vec::new();
+
+		for entry in read_dir(self.procfs_path("task"))
+			.map_err(|e| psutil_error_to_process_error(e, self.pid))?
+		{
+			let id = match entry.file_name().to_string_lossy().parse::<Pid>() {
+				Ok(id) => id,
+				Err(_) => continue,
+			};
+
+			match Thread::new(self.pid, id) {
+				Ok(thread) => threads.push(thread),
+				// the thread exited after the directory was listed
+				Err(ProcessError::NoSuchProcess { .. }) => continue,
+				Err(e) => return Err(e),
+			}
+		}
+
+		Ok(threads)
 	}
 
 	pub(crate) fn sys_cpu_times(&self) -> ProcessResult<ProcessCpuTimes> {
@@ -112,16 +174,23 @@ impl Process {
 		Ok(self.procfs_statm()?.into())
 	}
 
-	pub(crate) fn sys_memory_full_info(&self) {
-		todo!()
+	pub(crate) fn sys_memory_full_info(&self) -> ProcessResult<MemoryFullInfo> {
+		Ok((self.procfs_statm()?, procfs_smaps(self.pid)?).into())
 	}
 
-	pub(crate) fn sys_memory_percent_with_type(&self, _type: MemType) -> ProcessResult<Percent> {
-		todo!()
-	}
-
-	pub(crate) fn sys_children(&self) {
-		todo!()
+	pub(crate) fn sys_memory_with_type(&self, r#type: MemType) -> ProcessResult<Bytes> {
+		let bytes = match r#type {
+			MemType::Rss => self.memory_info()?.rss(),
+			MemType::Vms => self.memory_info()?.vms(),
+			MemType::Uss => procfs_smaps(self.pid)?.uss(),
+			MemType::Pss => procfs_smaps(self.pid)?.pss,
+			MemType::Swap => procfs_smaps(self.pid)?.swap,
+			MemType::Shared => self.memory_info()?.shared(),
+			MemType::Text => self.memory_info()?.text(),
+			MemType::Data => self.memory_info()?.data(),
+		};
+
+		Ok(bytes)
 	}
 
 	pub(crate) fn sys_open_files(&self) -> ProcessResult<Vec<OpenFile>> {
@@ -150,16 +219,33 @@ impl Process {
 			.collect()
 	}
 
-	pub(crate) fn sys_connections(&self) {
-		todo!()
+	pub(crate) fn sys_connections(&self) -> ProcessResult<Vec<NetConnection>> {
+		self.sys_connections_with_type(NetConnectionType::Inet)
 	}
 
-	pub(crate) fn sys_connections_with_type(&self, _type: NetConnectionType) {
-		todo!()
-	}
+	pub(crate) fn sys_connections_with_type(
+		&self,
+		type_: NetConnectionType,
+	) -> ProcessResult<Vec<NetConnection>> {
+		let inodes =
+			socket_inodes(self.pid).map_err(|e| psutil_error_to_process_error(e, self.pid))?;
+		if inodes.is_empty() {
+			return Ok(Vec::new());
+		}
+
+		// use the tables of the network namespace of the process
+		let connections = procfs_net_connections(&self.procfs_path("net"), type_)
+			.map_err(|e| psutil_error_to_process_error(e, self.pid))?;
 
-	pub(crate) fn sys_wait(&self) {
-		todo!()
+		Ok(connections
+			.into_iter()
+			.filter_map(|mut connection| {
+				connection.fd = Some(*inodes.get(&connection.inode)?);
+				connection.pid = Some(self.pid);
+
+				Some(connection)
+			})
+			.collect())
 	}
 }
 
@@ -0,0 +1,32 @@
+use std::time::{Duration, Instant};
+
+use crate::process::os::linux::procfs_task_stat;
+use crate::process::{ProcessCpuTimes, ProcessResult, Thread};
+use crate::Pid;
+
+impl Thread {
+	pub(crate) fn sys_new(pid: Pid, id: Pid) -> ProcessResult<Thread> {
+		let procfs_stat = procfs_task_stat(pid, id)?;
+
+		// children times are only reported for the whole process
+		let cpu_times = ProcessCpuTimes {
+			children_user: Duration::default(),
+			children_system: Duration::default(),
+			..ProcessCpuTimes::from(&procfs_stat)
+		};
+		let busy = cpu_times.busy();
+		let instant = Instant::now();
+
+		Ok(Thread {
+			pid,
+			id,
+			create_time: procfs_stat.starttime,
+			name: procfs_stat.comm,
+			status: procfs_stat.state,
+			cpu_times,
+			cpu_num: procfs_stat.processor as u32,
+			busy,
+			instant,
+		})
+	}
+}
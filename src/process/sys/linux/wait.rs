@@ -0,0 +1,99 @@
+use std::cmp;
+use std::io;
+use std::os::unix::process::ExitStatusExt;
+use std::process::ExitStatus;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use nix::libc;
+
+use crate::process::{
+	io_error_to_process_error, pidfd_open, poll_pidfd, remaining, Process, ProcessError,
+	ProcessResult,
+};
+use crate::Pid;
+
+/// Longest sleep between checks when falling back to polling, as in Python psutil.
+const MAX_POLL_INTERVAL: Duration = Duration::from_millis(40);
+
+/// Reaps the process without blocking if it is an exited child of the current process.
+///
+/// Returns `Ok(None)` if it is still running and `Err` with `ECHILD` if it is not a child.
+fn try_waitpid(pid: Pid) -> io::Result<Option<ExitStatus>> {
+	let mut status = 0;
+
+	loop {
+		match unsafe { libc::waitpid(pid as libc::pid_t, &mut status, libc::WNOHANG) } {
+			0 => return Ok(None),
+			-1 => {
+				let e = io::Error::last_os_error();
+				if e.kind() != io::ErrorKind::Interrupted {
+					return Err(e);
+				}
+			}
+			_ => return Ok(Some(ExitStatus::from_raw(status))),
+		}
+	}
+}
+
+impl Process {
+	pub(crate) fn sys_wait(&self, timeout: Option<Duration>) -> ProcessResult<Option<ExitStatus>> {
+		let deadline = timeout.map(|timeout| Instant::now() + timeout);
+		let to_process_error = |e: io::Error| io_error_to_process_error(e, self.pid);
+		let timeout_expired = || ProcessError::TimeoutExpired { pid: self.pid };
+
+		if !self.is_running() {
+			return Ok(None);
+		}
+
+		// use the pidfd held by the process, or open one for the duration of the wait
+		let opened = match self.pidfd {
+			Some(_) => None,
+			None => pidfd_open(self.pid).ok(),
+		};
+
+		match self.pidfd.as_deref().or(opened.as_ref()) {
+			Some(pidfd) => {
+				// the PID may have been reused before the pidfd was opened
+				if opened.is_some() && !self.is_running() {
+					return Ok(None);
+				}
+
+				if !poll_pidfd(pidfd, deadline).map_err(to_process_error)? {
+					return Err(timeout_expired());
+				}
+
+				match try_waitpid(self.pid) {
+					Ok(status) => Ok(status),
+					Err(e) if e.raw_os_error() == Some(libc::ECHILD) => Ok(None),
+					Err(e) => Err(to_process_error(e)),
+				}
+			}
+			// pidfds are not available, so poll until the process is gone
+			None => {
+				let mut interval = Duration::from_millis(1);
+
+				loop {
+					match try_waitpid(self.pid) {
+						Ok(Some(status)) => return Ok(Some(status)),
+						Ok(None) => {}
+						Err(e) if e.raw_os_error() == Some(libc::ECHILD) => {
+							if !self.is_running() {
+								return Ok(None);
+							}
+						}
+						Err(e) => return Err(to_process_error(e)),
+					}
+
+					let sleep = match remaining(deadline) {
+						Some(remaining) if remaining.is_zero() => return Err(timeout_expired()),
+						Some(remaining) => cmp::min(interval, remaining),
+						None => interval,
+					};
+					thread::sleep(sleep);
+					interval = cmp::min(interval * 2, MAX_POLL_INTERVAL);
+				}
+			}
+		}
+	}
+}
@@ -1,6 +1,7 @@
 mod pids;
 mod process;
 mod status;
+mod thread;
 
 pub use pids::*;
 pub use process::*;
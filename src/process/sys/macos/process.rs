@@ -4,17 +4,19 @@
 use std::convert::TryFrom;
 use std::ffi::CStr;
 use std::path::PathBuf;
+use std::process::ExitStatus;
 use std::time::{Duration, Instant};
 
 use nix::libc;
 
 use crate::common::NetConnectionType;
+use crate::network::NetConnection;
 use crate::process::os::macos::{kinfo_proc, kinfo_process, kinfo_processes};
 use crate::process::{
-	io_error_to_process_error, psutil_error_to_process_error, MemType, MemoryInfo, OpenFile,
-	Process, ProcessCpuTimes, ProcessError, ProcessResult, Status,
+	io_error_to_process_error, psutil_error_to_process_error, MemType, MemoryFullInfo, MemoryInfo,
+	OpenFile, Process, ProcessCpuTimes, ProcessError, ProcessResult, Status, Thread,
 };
-use crate::{Count, Error, Percent, Pid, Result};
+use crate::{Bytes, Count, Error, Pid, Result};
 
 fn catch_zombie(proc_err: ProcessError) -> ProcessError {
 	if let ProcessError::PsutilError {
@@ -107,10 +109,6 @@ impl Process {
 		todo!()
 	}
 
-	pub(crate) fn sys_parents(&self) -> Option<Vec<Process>> {
-		todo!()
-	}
-
 	pub(crate) fn sys_status(&self) -> ProcessResult<Status> {
 		todo!()
 	}
@@ -119,27 +117,27 @@ impl Process {
 		todo!()
 	}
 
-	pub(crate) fn sys_username(&self) -> String {
+	pub(crate) fn sys_username(&self) -> ProcessResult<String> {
 		todo!()
 	}
 
-	pub(crate) fn sys_get_nice(&self) -> i32 {
+	pub(crate) fn sys_get_nice(&self) -> ProcessResult<i32> {
 		todo!()
 	}
 
-	pub(crate) fn sys_set_nice(&self, _nice: i32) {
+	pub(crate) fn sys_set_nice(&self, _nice: i32) -> ProcessResult<()> {
 		todo!()
 	}
 
-	pub(crate) fn sys_num_ctx_switches(&self) -> Count {
+	pub(crate) fn sys_num_ctx_switches(&self) -> ProcessResult<Count> {
 		todo!()
 	}
 
-	pub(crate) fn sys_num_threads(&self) -> Count {
+	pub(crate) fn sys_num_threads(&self) -> ProcessResult<Count> {
 		todo!()
 	}
 
-	pub(crate) fn sys_threads(&self) {
+	pub(crate) fn sys_threads(&self) -> ProcessResult<Vec<Thread>> {
 		todo!()
 	}
 
@@ -153,15 +151,11 @@ impl Process {
 			.map_err(|e| catch_zombie(io_error_to_process_error(e, self.pid)))
 	}
 
-	pub(crate) fn sys_memory_full_info(&self) {
-		todo!()
-	}
-
-	pub(crate) fn sys_memory_percent_with_type(&self, _type: MemType) -> ProcessResult<Percent> {
+	pub(crate) fn sys_memory_full_info(&self) -> ProcessResult<MemoryFullInfo> {
 		todo!()
 	}
 
-	pub(crate) fn sys_children(&self) {
+	pub(crate) fn sys_memory_with_type(&self, _type: MemType) -> ProcessResult<Bytes> {
 		todo!()
 	}
 
@@ -169,15 +163,18 @@ impl Process {
 		todo!()
 	}
 
-	pub(crate) fn sys_connections(&self) {
+	pub(crate) fn sys_connections(&self) -> ProcessResult<Vec<NetConnection>> {
 		todo!()
 	}
 
-	pub(crate) fn sys_connections_with_type(&self, _type: NetConnectionType) {
+	pub(crate) fn sys_connections_with_type(
+		&self,
+		_type: NetConnectionType,
+	) -> ProcessResult<Vec<NetConnection>> {
 		todo!()
 	}
 
-	pub(crate) fn sys_wait(&self) {
+	pub(crate) fn sys_wait(&self, _timeout: Option<Duration>) -> ProcessResult<Option<ExitStatus>> {
 		todo!()
 	}
 }
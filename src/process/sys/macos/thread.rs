@@ -0,0 +1,8 @@
+use crate::process::{ProcessResult, Thread};
+use crate::Pid;
+
+impl Thread {
+	pub(crate) fn sys_new(_pid: Pid, _id: Pid) -> ProcessResult<Thread> {
+		todo!()
+	}
+}
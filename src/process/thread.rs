@@ -0,0 +1,112 @@
+use std::cmp;
+use std::hash::{Hash, Hasher};
+use std::time::{Duration, Instant};
+
+use crate::process::{ProcessCpuTimes, ProcessResult, Status};
+use crate::utils::duration_percent;
+use crate::{Percent, Pid};
+
+/// A thread of a process, as returned by `Process::threads`.
+///
+/// Python psutil only returns the id and CPU times of a thread.
+#[derive(Clone, Debug)]
+pub struct Thread {
+	pub(crate) pid: Pid,
+	pub(crate) id: Pid,
+	pub(crate) create_time: Duration,
+	pub(crate) name: String,
+	pub(crate) status: Status,
+	pub(crate) cpu_times: ProcessCpuTimes,
+	pub(crate) cpu_num: u32,
+	pub(crate) busy: Duration,
+	pub(crate) instant: Instant,
+}
+
+impl Thread {
+	pub fn new(pid: Pid, id: Pid) -> ProcessResult<Thread> {
+		Thread::sys_new(pid, id)
+	}
+
+	/// PID of the process the thread belongs to.
+	pub fn pid(&self) -> Pid {
+		self.pid
+	}
+
+	/// The thread ID.
+	pub fn id(&self) -> Pid {
+		self.id
+	}
+
+	pub fn create_time(&self) -> Duration {
+		self.create_time
+	}
+
+	/// The thread name at the time of the last update.
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+
+	/// The thread status at the time of the last update.
+	pub fn status(&self) -> Status {
+		self.status
+	}
+
+	/// The thread CPU times at the time of the last update.
+	/// Children times are always zero.
+	pub fn cpu_times(&self) -> &ProcessCpuTimes {
+		&self.cpu_times
+	}
+
+	/// The CPU the thread last ran on, at the time of the last update.
+	pub fn cpu_num(&self) -> u32 {
+		self.cpu_num
+	}
+
+	/// Re-reads the name, status, CPU times and CPU number of the thread.
+	pub fn update(&mut self) -> ProcessResult<()> {
+		let thread = Thread::new(self.pid, self.id)?;
+
+		self.name = thread.name;
+		self.status = thread.status;
+		self.cpu_times = thread.cpu_times;
+		self.cpu_num = thread.cpu_num;
+
+		Ok(())
+	}
+
+	/// Returns the cpu percent since the thread was created, replaced, or since the last time this
+	/// method was called.
+	/// Also updates the other fields of the thread.
+	pub fn cpu_percent(&mut self) -> ProcessResult<Percent> {
+		self.update()?;
+
+		let busy = self.cpu_times.busy();
+		let instant = Instant::now();
+
+		let percent = duration_percent(
+			busy.checked_sub(self.busy).unwrap_or_default(),
+			instant - self.instant,
+		);
+
+		self.busy = busy;
+		self.instant = instant;
+
+		Ok(percent)
+	}
+}
+
+impl PartialEq for Thread {
+	// Compares threads using their id and create_time as a unique identifier.
+	fn eq(&self, other: &Thread) -> bool {
+		(self.id() == other.id()) && (self.create_time() == other.create_time())
+	}
+}
+
+impl cmp::Eq for Thread {}
+
+impl Hash for Thread {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.id().hash(state);
+		self.create_time().hash(state);
+	}
+}
@@ -0,0 +1,181 @@
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+use crate::process::{self, Process};
+use crate::{Pid, Result};
+
+/// New struct, not in Python psutil.
+///
+/// A snapshot of the parent/child relationships of all processes, built from a single scan of
+/// the process list. Lookups do not rescan the process list, so the result can be out of date
+/// by the time it is used.
+#[derive(Debug, Clone)]
+pub struct ProcessTree {
+	processes: BTreeMap<Pid, Process>,
+	parents: BTreeMap<Pid, Pid>,
+	children: BTreeMap<Pid, Vec<Pid>>,
+}
+
+impl ProcessTree {
+	pub fn new() -> Result<ProcessTree> {
+		let processes = process::processes()?
+			.into_iter()
+			.filter_map(|process| process.ok());
+
+		Ok(ProcessTree::from_processes(processes))
+	}
+
+	/// Builds the tree from an existing list of processes, e.g. the processes of a
+	/// `ProcessCollector`.
+	pub fn from_processes<I>(processes: I) -> ProcessTree
+	where
+		I: IntoIterator<Item = Process>,
+	{
+		let processes: BTreeMap<Pid, Process> = processes
+			.into_iter()
+			.map(|process| (process.pid(), process))
+			.collect();
+
+		let mut parents = BTreeMap::new();
+		let mut children: BTreeMap<Pid, Vec<Pid>> = BTreeMap::new();
+
+		for (pid, process) in &processes {
+			#[cfg(target_os = "linux")]
+			let ppid = process.procfs_stat.ppid;
+			#[cfg(not(target_os = "linux"))]
+			let ppid = process.ppid().ok().flatten();
+
+			let parent = match ppid.and_then(|ppid| processes.get(&ppid)) {
+				Some(parent) => parent,
+				None => continue,
+			};
+
+			// The parent PID may have been reused by a process that was created after the child.
+			if parent.pid() == *pid || parent.create_time() > process.create_time() {
+				continue;
+			}
+
+			parents.insert(*pid, parent.pid());
+			children.entry(parent.pid()).or_default().push(*pid);
+		}
+
+		ProcessTree {
+			processes,
+			parents,
+			children,
+		}
+	}
+
+	/// All processes in the tree.
+	pub fn processes(&self) -> &BTreeMap<Pid, Process> {
+		&self.processes
+	}
+
+	pub fn get(&self, pid: Pid) -> Option<&Process> {
+		self.processes.get(&pid)
+	}
+
+	/// Processes whose parent is not part of the tree, e.g. `init` and `kthreadd` on Linux.
+	pub fn roots(&self) -> Vec<&Process> {
+		self.processes
+			.values()
+			.filter(|process| !self.parents.contains_key(&process.pid()))
+			.collect()
+	}
+
+	pub fn parent(&self, pid: Pid) -> Option<&Process> {
+		self.parents.get(&pid).map(|ppid| &self.processes[ppid])
+	}
+
+	/// The parent, grandparent, and so on, ending with a root of the tree.
+	pub fn ancestors(&self, pid: Pid) -> Vec<&Process> {
+		let mut ancestors = Vec::new();
+		let mut current = pid;
+
+		while let Some(ppid) = self.parents.get(&current) {
+			ancestors.push(&self.processes[ppid]);
+			current = *ppid;
+		}
+
+		ancestors
+	}
+
+	/// The number of ancestors of a process, so roots have a depth of 0.
+	/// Returns `None` if the process is not part of the tree.
+	pub fn depth(&self, pid: Pid) -> Option<usize> {
+		if !self.processes.contains_key(&pid) {
+			return None;
+		}
+
+		Some(self.ancestors(pid).len())
+	}
+
+	/// The direct children of a process.
+	pub fn children(&self, pid: Pid) -> Vec<&Process> {
+		self.children
+			.get(&pid)
+			.map(|children| children.iter().map(|pid| &self.processes[pid]).collect())
+			.unwrap_or_default()
+	}
+
+	/// All descendants of a process in breadth-first order, not including the process itself.
+	pub fn descendants(&self, pid: Pid) -> Vec<&Process> {
+		let mut descendants = Vec::new();
+		let mut visited = BTreeSet::new();
+		let mut queue: VecDeque<Pid> = VecDeque::new();
+		queue.push_back(pid);
+
+		while let Some(current) = queue.pop_front() {
+			for child in self.children.get(&current).into_iter().flatten() {
+				if visited.insert(*child) {
+					descendants.push(&self.processes[child]);
+					queue.push_back(*child);
+				}
+			}
+		}
+
+		descendants
+	}
+}
+
+#[cfg(test)]
+mod unit_tests {
+	use super::*;
+
+	#[test]
+	fn test_process_tree() {
+		// other tests spawn children in parallel, so only the known child is checked
+		let mut child = std::process::Command::new("sleep")
+			.arg("10")
+			.spawn()
+			.unwrap();
+		let tree = ProcessTree::new().unwrap();
+		let current = Process::current().unwrap();
+		let pid = current.pid();
+
+		assert!(tree.get(pid).is_some());
+		assert!(tree
+			.descendants(pid)
+			.iter()
+			.any(|descendant| descendant.pid() == child.id()));
+		child.kill().unwrap();
+		child.wait().unwrap();
+
+		let ancestors = tree.ancestors(pid);
+		assert_eq!(tree.depth(pid), Some(ancestors.len()));
+		if let Some(parent) = ancestors.first() {
+			assert_eq!(Some(parent.pid()), current.ppid().unwrap());
+			assert!(tree
+				.children(parent.pid())
+				.iter()
+				.any(|child| child.pid() == pid));
+			assert!(tree
+				.descendants(ancestors.last().unwrap().pid())
+				.iter()
+				.any(|descendant| descendant.pid() == pid));
+		}
+		assert!(tree
+			.roots()
+			.iter()
+			.all(|root| tree.depth(root.pid()) == Some(0)));
+	}
+}
@@ -0,0 +1,171 @@
+use std::process::ExitStatus;
+use std::time::{Duration, Instant};
+
+use crate::process::{Process, ProcessError, ProcessResult, Signal};
+
+#[cfg(target_os = "linux")]
+use crate::process::os::linux::ProcessExt as _;
+
+/// A process that exited while waiting in `wait_procs` or `terminate_tree`.
+#[derive(Clone, Debug)]
+pub struct GoneProcess {
+	pub process: Process,
+
+	/// Only known for children of the current process.
+	/// Renamed from `returncode` in Python psutil.
+	pub exit_status: Option<ExitStatus>,
+}
+
+/// Waits for the processes to exit, or until the timeout expires.
+///
+/// Returns the processes that are gone and the ones that are still alive. The callback is called
+/// for each process as soon as it is known to be gone. Processes that no longer exist count as
+/// gone.
+pub fn wait_procs<F>(
+	procs: Vec<Process>,
+	timeout: Option<Duration>,
+	mut callback: F,
+) -> ProcessResult<(Vec<GoneProcess>, Vec<Process>)>
+where
+	F: FnMut(&GoneProcess),
+{
+	let deadline = timeout.map(|timeout| Instant::now() + timeout);
+	let mut gone = Vec::new();
+	let mut alive = procs;
+
+	loop {
+		let mut still_alive = Vec::new();
+		// share the remaining time between the processes, as in Python psutil
+		let timeout = deadline.map(|deadline| {
+			deadline.saturating_duration_since(Instant::now()) / alive.len().max(1) as u32
+		});
+
+		for process in alive {
+			let exit_status = match process.wait(timeout) {
+				Ok(exit_status) => exit_status,
+				Err(ProcessError::NoSuchProcess { .. }) => None,
+				Err(ProcessError::TimeoutExpired { .. }) => {
+					still_alive.push(process);
+					continue;
+				}
+				Err(e) => return Err(e),
+			};
+
+			let gone_process = GoneProcess {
+				process,
+				exit_status,
+			};
+			callback(&gone_process);
+			gone.push(gone_process);
+		}
+
+		alive = still_alive;
+
+		let expired = match deadline {
+			Some(deadline) => Instant::now() >= deadline,
+			None => true,
+		};
+		if alive.is_empty() || expired {
+			return Ok((gone, alive));
+		}
+	}
+}
+
+/// New function, not in Python psutil.
+///
+/// Sends `SIGTERM` to the process and all of its descendants, waits up to `grace` for them to
+/// exit, then sends `SIGKILL` to the survivors and waits up to `grace` again.
+///
+/// Returns the processes that are gone and the ones that are still alive. Processes that cannot
+/// be signalled, e.g. due to insufficient permissions, are returned as alive.
+/// On Linux, pidfds are used where available so that reused PIDs are never signalled.
+pub fn terminate_tree(
+	root: &Process,
+	grace: Duration,
+) -> ProcessResult<(Vec<GoneProcess>, Vec<Process>)> {
+	// in breadth-first order, so parents come before their descendants
+	let mut procs = vec![root.clone()];
+	procs.extend(root.children(true)?);
+
+	#[cfg(target_os = "linux")]
+	{
+		for process in procs.iter_mut() {
+			// signalling falls back to comparing create times
+			let _ = process.open_pidfd();
+		}
+	}
+
+	// descendants are signalled before their parents
+	for process in procs.iter().rev() {
+		let _ = process.send_signal(Signal::SIGTERM);
+	}
+	let (mut gone, alive) = wait_procs(procs, Some(grace), |_| {})?;
+
+	if alive.is_empty() {
+		return Ok((gone, alive));
+	}
+
+	for process in alive.iter() {
+		let _ = process.send_signal(Signal::SIGKILL);
+	}
+	let (killed, alive) = wait_procs(alive, Some(grace), |_| {})?;
+	gone.extend(killed);
+
+	Ok((gone, alive))
+}
+
+#[cfg(test)]
+mod unit_tests {
+	use super::*;
+
+	#[test]
+	fn test_wait_procs() {
+		let mut short = std::process::Command::new("true").spawn().unwrap();
+		let mut long = std::process::Command::new("sleep")
+			.arg("10")
+			.spawn()
+			.unwrap();
+		let procs = vec![
+			Process::new(short.id()).unwrap(),
+			Process::new(long.id()).unwrap(),
+		];
+
+		let mut called = Vec::new();
+		let (gone, alive) = wait_procs(procs, Some(Duration::from_millis(500)), |gone| {
+			called.push(gone.process.pid())
+		})
+		.unwrap();
+
+		assert_eq!(called, vec![short.id()]);
+		assert_eq!(gone.len(), 1);
+		assert!(gone[0].exit_status.unwrap().success());
+		assert_eq!(alive.len(), 1);
+		assert_eq!(alive[0].pid(), long.id());
+
+		long.kill().unwrap();
+		long.wait().unwrap();
+		assert!(short.try_wait().is_err());
+	}
+
+	#[test]
+	fn test_terminate_tree() {
+		// the shell ignores SIGTERM, so it has to be killed
+		let mut child = std::process::Command::new("sh")
+			.args(["-c", "trap '' TERM; sleep 10 & wait"])
+			.spawn()
+			.unwrap();
+		let root = Process::new(child.id()).unwrap();
+		let deadline = Instant::now() + Duration::from_secs(5);
+		while root.children(false).unwrap().is_empty() {
+			assert!(Instant::now() < deadline, "sleep was not spawned");
+			std::thread::sleep(Duration::from_millis(10));
+		}
+
+		let (gone, alive) = terminate_tree(&root, Duration::from_millis(200)).unwrap();
+
+		assert!(alive.is_empty());
+		assert!(gone.len() >= 2);
+		assert!(gone.iter().any(|gone| gone.process == root));
+		assert!(child.try_wait().is_err());
+	}
+}